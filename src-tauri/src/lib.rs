@@ -1,8 +1,160 @@
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use tauri::{Emitter, Manager};
+use tracing::{debug, error, info, warn};
+
+#[derive(Debug, Clone)]
+pub enum AppError {
+    Network(String),
+    Auth(String),
+    NotFound(String),
+    RateLimited { reset: Option<String>, message: String },
+    HasDependents { dependents: Vec<String>, message: String },
+    Io(String),
+    Parse(String),
+    Invalid(String),
+}
+
+impl AppError {
+    fn kind(&self) -> &'static str {
+        match self {
+            AppError::Network(_) => "Network",
+            AppError::Auth(_) => "Auth",
+            AppError::NotFound(_) => "NotFound",
+            AppError::RateLimited { .. } => "RateLimited",
+            AppError::HasDependents { .. } => "HasDependents",
+            AppError::Io(_) => "Io",
+            AppError::Parse(_) => "Parse",
+            AppError::Invalid(_) => "Invalid",
+        }
+    }
+    
+    fn message(&self) -> &str {
+        match self {
+            AppError::Network(message)
+            | AppError::Auth(message)
+            | AppError::NotFound(message)
+            | AppError::Io(message)
+            | AppError::Parse(message)
+            | AppError::Invalid(message) => message,
+            AppError::RateLimited { message, .. } => message,
+            AppError::HasDependents { message, .. } => message,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+// Errors cross the Tauri boundary as { kind, message, reset?, dependents? } so the UI can react to the kind
+impl Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", self.message())?;
+        match self {
+            AppError::RateLimited { reset, .. } => state.serialize_field("reset", reset)?,
+            AppError::HasDependents { dependents, .. } => state.serialize_field("dependents", dependents)?,
+            _ => {}
+        }
+        state.end()
+    }
+}
+
+// Maximum number of update checks running at the same time
+const UPDATE_CHECK_CONCURRENCY: usize = 8;
+
+// Default number of mod downloads running at the same time during batch updates
+const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 3;
+
+// Log files are named stardew-mod-manager.<date>.log and kept for a week
+const LOG_FILE_PREFIX: &str = "stardew-mod-manager";
+const MAX_LOG_FILES: usize = 7;
+
+// First delay between retried network requests, doubled on every attempt
+const RETRY_BASE_DELAY_MS: u64 = 500;
+
+// Suffix of the copy update_mod keeps while replacing a mod
+const BACKUP_SUFFIX: &str = ".backup";
+
+// How many plain group folders deep scan_mods looks for mods
+const MAX_MOD_GROUP_DEPTH: usize = 1;
+
+// How many folders deep find_mod_containing_file looks inside a mod
+const MAX_FILE_SEARCH_DEPTH: usize = 8;
+
+// Mods installed by SMAPI itself, left alone by bulk enable/disable
+const SMAPI_BUNDLED_MODS: [&str; 3] = ["ConsoleCommands", "SaveBackup", "ErrorHandler"];
+
+// Frameworks content packs are most often made for, to name them when they aren't installed
+const KNOWN_FRAMEWORKS: [(&str, &str); 10] = [
+    ("Pathoschild.ContentPatcher", "Content Patcher"),
+    ("spacechase0.JsonAssets", "Json Assets"),
+    ("spacechase0.DynamicGameAssets", "Dynamic Game Assets"),
+    ("Esca.FarmTypeManager", "Farm Type Manager"),
+    ("Digus.ProducerFrameworkMod", "Producer Framework Mod"),
+    ("DIGUS.MailFrameworkMod", "Mail Framework Mod"),
+    ("Platonymous.TMXLoader", "TMXL Map Toolkit"),
+    ("Platonymous.CustomFarmingRedux", "Custom Farming Redux"),
+    ("PeacefulEnd.AlternativeTextures", "Alternative Textures"),
+    ("furyx639.ExpandedStorage", "Expanded Storage"),
+];
+
+// Bumped whenever the exported mod list layout changes
+const MOD_LIST_FORMAT_VERSION: u32 = 1;
+
+// Where the manager's own releases are published
+const APP_REPOSITORY: &str = "psyycker/Stardew-Mods-Manager";
+
+// Sibling of the Mods folder where staged mods are kept out of SMAPI's reach
+const STAGING_FOLDER_NAME: &str = "Mods (disabled)";
+
+// Community compatibility list, refreshed at most once a day
+const COMPATIBILITY_LIST_URL: &str = "https://smapi.io/mods/export";
+const COMPATIBILITY_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+// SMAPI's own update check service, resolves update keys for every provider in one request
+const SMAPI_API_URL: &str = "https://smapi.io/api/v3.0/mods";
+
+// Update downloads in the temp dir, the prefix lets cleanup find only our own files
+const TEMP_DOWNLOAD_PREFIX: &str = "smm-";
+
+// Downloads younger than this may still be in progress, cleanup leaves them alone
+const TEMP_DOWNLOAD_MIN_AGE_SECS: u64 = 60 * 60;
+
+// Written into a mod folder to mark it disabled when folders aren't renamed
+const DISABLED_MARKER: &str = ".smapi-disabled";
+const MARKER_STILL_LOADED: &str = "Disabled in the manager only, SMAPI still loads this mod";
+
+// Marker file next to the executable that keeps all app data beside it
+const PORTABLE_MARKER: &str = "portable.txt";
+
+// App config directory from Tauri's path resolver, set during setup
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// Shared HTTP client so connections are pooled, rebuilt when the timeout or proxy settings change
+static HTTP_CLIENT: Mutex<Option<(HttpClientConfig, reqwest::Client)>> = Mutex::new(None);
+
+// Keeps the background log writer alive for the whole run
+static LOG_GUARD: OnceLock<tracing_appender::non_blocking::WorkerGuard> = OnceLock::new();
+
+// Swaps the log filter at runtime when the user changes the log level
+static LOG_FILTER: OnceLock<tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>> = OnceLock::new();
+
+// Serializes settings writes so the backup and the replace of one save don't interleave with another
+static SETTINGS_WRITE: Mutex<()> = Mutex::new(());
+
+// Last computed folder size per mod, keyed by the enabled folder path
+static MOD_SIZE_CACHE: LazyLock<Mutex<HashMap<PathBuf, u64>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModInfo {
@@ -13,6 +165,132 @@ pub struct ModInfo {
     pub folder_name: String,
     pub enabled: bool,
     pub update_keys: Vec<String>,
+    #[serde(default)]
+    pub unique_id: String,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+    #[serde(default)]
+    pub content_pack_for: Option<String>,
+    #[serde(default)]
+    pub minimum_api_version: Option<String>,
+    #[serde(default)]
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub mod_type: ModType,
+    #[serde(default)]
+    pub has_config: bool,
+    // Why SMAPI would refuse to load the mod, None when it looks fine
+    #[serde(default)]
+    pub invalid: Option<String>,
+    // Lives in the staging folder, folder_name is then relative to it
+    #[serde(default)]
+    pub staged: bool,
+    // Files sit loose at the root of the Mods folder, folder_name is then empty
+    #[serde(default)]
+    pub needs_folder: bool,
+    // Changes whenever a file is added, removed or modified, only filled on request
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    // Priority hint some frameworks read from the manifest, lower loads first
+    #[serde(default)]
+    pub load_order: Option<i64>,
+    // SMAPI loads every folder without a dot prefix, even one disabled through the marker mechanism
+    #[serde(default = "default_true")]
+    pub loaded: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LoadOrderSuggestion {
+    // Folder names, every mod after the mods it depends on
+    pub order: Vec<String>,
+    // Folder names of mods depending on each other in a loop
+    pub cycles: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModType {
+    SmapiDll,
+    ContentPatcher,
+    Xnb,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependency {
+    pub unique_id: String,
+    pub minimum_version: Option<String>,
+    pub is_required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Version")]
+    pub version: Option<String>,
+    #[serde(rename = "Author")]
+    pub author: Option<String>,
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+    #[serde(rename = "UpdateKeys", default, deserialize_with = "deserialize_update_keys")]
+    pub update_keys: Vec<String>,
+    #[serde(rename = "UniqueID", alias = "UniqueId")]
+    pub unique_id: Option<String>,
+    #[serde(rename = "Dependencies", default)]
+    pub dependencies: Vec<ManifestDependency>,
+    #[serde(rename = "ContentPackFor")]
+    pub content_pack_for: Option<ManifestContentPackFor>,
+    #[serde(rename = "MinimumApiVersion")]
+    pub minimum_api_version: Option<String>,
+    #[serde(rename = "EntryDll")]
+    pub entry_dll: Option<String>,
+    #[serde(rename = "LoadOrder", alias = "Priority", default, deserialize_with = "deserialize_load_order")]
+    pub load_order: Option<i64>,
+}
+
+// Some manifests write a single key as a plain string instead of an array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UpdateKeysField {
+    One(String),
+    Many(Vec<String>),
+    Missing(()),
+}
+
+fn deserialize_update_keys<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let keys = match UpdateKeysField::deserialize(deserializer)? {
+        UpdateKeysField::One(key) => vec![key],
+        UpdateKeysField::Many(keys) => keys,
+        UpdateKeysField::Missing(()) => Vec::new(),
+    };
+    Ok(keys.into_iter().map(|key| key.trim().to_string()).filter(|key| !key.is_empty()).collect())
+}
+
+// Not a SMAPI field, so anything but a number is ignored instead of breaking the manifest
+fn deserialize_load_order<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(value.as_i64().or_else(|| value.as_str().and_then(|text| text.trim().parse().ok())))
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestDependency {
+    #[serde(rename = "UniqueID", alias = "UniqueId", default)]
+    pub unique_id: String,
+    #[serde(rename = "MinimumVersion")]
+    pub minimum_version: Option<String>,
+    #[serde(rename = "IsRequired", default = "default_true")]
+    pub is_required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestContentPackFor {
+    #[serde(rename = "UniqueID", alias = "UniqueId", default)]
+    pub unique_id: String,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,569 +298,5580 @@ pub struct StardewInfo {
     pub game_path: Option<PathBuf>,
     pub mods_path: Option<PathBuf>,
     pub found: bool,
+    // Set when the install location is likely to get in the way of modding
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModVerification {
+    pub folder_name: String,
+    pub healthy: bool,
+    pub problems: Vec<VerifyProblem>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum VerifyProblem {
+    EmptyFolder,
+    MissingManifest,
+    InvalidManifest { message: String },
+    // SMAPI needs either an EntryDll or a ContentPackFor to load a mod
+    NoEntryPoint,
+    MissingEntryDll { file: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupFolder {
+    pub folder_name: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BackupCleanupResult {
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanComplete {
+    pub count: usize,
+    pub order: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModsPathValidation {
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SmapiInfo {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub executable_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SmapiModError {
+    pub mod_name: String,
+    pub reason: String,
+    // Only known when the name matches one of the installed mods
+    pub unique_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub update_status: UpdateStatus,
+    // Update key that produced this result, empty when none did
+    #[serde(default)]
+    pub checked_via: String,
+    // "<update key>: <reason>" for every key that was tried and failed first
+    #[serde(default)]
+    pub failed_checks: Vec<String>,
+    // When the Nexus hourly limit resets, set for mods that couldn't be checked because of it
+    #[serde(default)]
+    pub rate_limit_reset: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpdateStatus {
+    #[default]
+    UpToDate,
+    Available,
+    NoUpdateKey,
+    ManualCheck,
+    Offline,
+    Pinned,
+    RateLimited,
+    Error,
+}
+
+impl UpdateStatus {
+    fn from_update_available(update_available: bool) -> Self {
+        if update_available {
+            UpdateStatus::Available
+        } else {
+            UpdateStatus::UpToDate
+        }
+    }
+}
+
+impl UpdateInfo {
+    // Result for a mod that couldn't be checked, the installed version is all we know
+    fn unchecked(current_version: &str, update_status: UpdateStatus) -> Self {
+        UpdateInfo {
+            current_version: current_version.to_string(),
+            latest_version: current_version.to_string(),
+            update_available: false,
+            download_url: None,
+            update_status,
+            checked_via: String::new(),
+            failed_checks: Vec::new(),
+            rate_limit_reset: None,
+        }
+    }
+    
+    // The same remote result compared against another installed version
+    fn for_version(self, current_version: &str) -> Self {
+        if self.update_status == UpdateStatus::ManualCheck {
+            return UpdateInfo {
+                current_version: current_version.to_string(),
+                ..self
+            };
+        }
+        
+        let update_available = version_compare(current_version, &self.latest_version);
+        UpdateInfo {
+            current_version: current_version.to_string(),
+            update_available,
+            update_status: UpdateStatus::from_update_available(update_available),
+            ..self
+        }
+    }
+    
+    // Result for a source without an API, the user has to look at the page themselves
+    fn manual_check(current_version: &str, download_url: Option<String>) -> Self {
+        UpdateInfo {
+            current_version: current_version.to_string(),
+            latest_version: "Manual check".to_string(),
+            update_available: false,
+            download_url,
+            update_status: UpdateStatus::ManualCheck,
+            checked_via: String::new(),
+            failed_checks: Vec::new(),
+            rate_limit_reset: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NexusModInfo {
+    pub version: String,
+    pub mod_id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NexusModDetails {
+    pub mod_id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub picture_url: Option<String>,
+    #[serde(default)]
+    pub endorsement_count: u64,
+    #[serde(default)]
+    pub updated_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NexusUser {
+    #[serde(default)]
+    pub is_premium: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct NexusTrackedMod {
+    pub mod_id: u32,
+    #[serde(default)]
+    pub domain_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NexusDownloadLink {
+    #[serde(rename = "URI")]
+    pub uri: String,
+    #[serde(default)]
+    pub short_name: String,
+}
+
+// Premium accounts get the file installed, free accounts have to go through the website
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum NexusDownloadResult {
+    Installed { message: String },
+    ManualDownload { url: String },
+}
+
+#[derive(Debug)]
+struct NxmLink {
+    pub mod_id: u32,
+    pub file_id: u32,
+    pub key: Option<String>,
+    pub expires: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModUpdatedEvent {
+    pub folder_name: String,
+    pub success: bool,
+    pub version: Option<String>,
+    pub error: Option<AppError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NxmDownloadEvent {
+    pub mod_id: u32,
+    pub file_id: u32,
+    pub success: bool,
+    pub message: String,
+    pub manual_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NexusFileInfo {
+    pub file_id: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+    #[serde(default)]
+    pub category_name: Option<String>,
+    #[serde(default)]
+    pub uploaded_timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct NexusFilesResponse {
+    #[serde(default)]
+    pub files: Vec<NexusFileInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GitHubRelease {
+    pub tag_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub folder_name: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionProgress {
+    pub folder_name: String,
+    pub files_done: usize,
+    pub files_total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionComplete {
+    pub folder_name: String,
+    pub success: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdatePreview {
+    pub files: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModsBackupResult {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModUpdateResult {
+    pub folder_name: String,
+    pub success: bool,
+    pub message: String,
+    pub changes: Option<UpdateChanges>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTarget {
+    pub folder_name: String,
+    pub download_url: String,
+    #[serde(default)]
+    pub expected_md5: Option<String>,
+    #[serde(default)]
+    pub target_version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkUpdateSummary {
+    pub succeeded: Vec<ModUpdateResult>,
+    pub failed: Vec<ModUpdateResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateModGroup {
+    pub unique_id: String,
+    pub folder_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContentConflict {
+    pub target: String,
+    pub folder_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileMatch {
+    pub folder_name: String,
+    pub mod_name: String,
+    // Relative to the mod folder, always with forward slashes
+    pub relative_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissingFramework {
+    pub unique_id: String,
+    // From the installed copy or the list of well known frameworks, None when unknown
+    pub name: Option<String>,
+    // Installed, but disabled so SMAPI won't load it
+    pub disabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateProvider {
+    // Prefix used in manifest UpdateKeys, e.g. "Nexus:1234"
+    pub prefix: &'static str,
+    pub requires_api_key: bool,
+    // False when the app can only link to the mod page
+    pub automatic_check: bool,
+    // Whether versions are actually checked with the current settings
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreflightIssue {
+    // None for problems with the install itself rather than a mod
+    pub folder_name: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreflightReport {
+    pub mod_count: usize,
+    // Mods SMAPI will load, marker-disabled ones included
+    pub enabled_count: usize,
+    pub smapi_version: Option<String>,
+    pub errors: Vec<PreflightIssue>,
+    pub warnings: Vec<PreflightIssue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ModListExport {
+    pub format_version: u32,
+    pub mods: Vec<ExportedMod>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedMod {
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    #[serde(default)]
+    pub unique_id: String,
+    #[serde(default)]
+    pub update_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedMod {
+    #[serde(flatten)]
+    pub info: ExportedMod,
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppSettings {
+    pub nexus_api_key: Option<String>,
+    #[serde(default = "default_update_cache_ttl")]
+    pub update_cache_ttl_secs: u64,
+    #[serde(default)]
+    pub game_path: Option<PathBuf>,
+    #[serde(default)]
+    pub mods_path: Option<PathBuf>,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub include_prereleases: bool,
+    #[serde(default)]
+    pub offline: bool,
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout_secs: u64,
+    // Enabled folder name -> version the user wants to stay on
+    #[serde(default)]
+    pub pinned_versions: HashMap<String, String>,
+    #[serde(default)]
+    pub ui_state: UiState,
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    // Lowercased unique ID -> user annotations, survives folder renames
+    #[serde(default)]
+    pub mod_notes: HashMap<String, ModNote>,
+    // Level for the app's own logs, dependencies never go below info
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    // Marker only changes what the manager shows as disabled, SMAPI keeps loading those mods
+    #[serde(default)]
+    pub enable_mechanism: EnableMechanism,
+    // Lowercased unique IDs disabled through the marker mechanism
+    #[serde(default)]
+    pub disabled_mod_ids: Vec<String>,
+}
+
+// Rename is what SMAPI understands: it skips folders starting with a dot. Marker leaves folder
+// names alone and only records the state for the manager, SMAPI will still load those mods
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnableMechanism {
+    #[default]
+    Rename,
+    Marker,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModNote {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: String,
+}
+
+// Window geometry and navigation restored by the frontend on startup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub window_width: Option<f64>,
+    #[serde(default)]
+    pub window_height: Option<f64>,
+    #[serde(default)]
+    pub window_x: Option<f64>,
+    #[serde(default)]
+    pub window_y: Option<f64>,
+    #[serde(default)]
+    pub last_tab: String,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            nexus_api_key: None,
+            update_cache_ttl_secs: default_update_cache_ttl(),
+            game_path: None,
+            mods_path: None,
+            max_retries: default_max_retries(),
+            include_prereleases: false,
+            offline: false,
+            request_timeout_secs: default_request_timeout(),
+            pinned_versions: HashMap::new(),
+            ui_state: UiState::default(),
+            proxy_url: None,
+            mod_notes: HashMap::new(),
+            log_level: default_log_level(),
+            enable_mechanism: EnableMechanism::default(),
+            disabled_mod_ids: Vec::new(),
+        }
+    }
+}
+
+fn default_update_cache_ttl() -> u64 {
+    60 * 60
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_request_timeout() -> u64 {
+    30
+}
+
+fn default_log_level() -> String {
+    "debug".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModProfile {
+    pub name: String,
+    pub enabled_folders: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfilesFile {
+    pub profiles: Vec<ModProfile>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProfileApplyResult {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+    pub missing: Vec<String>,
+    pub unlisted: Vec<String>,
+    pub errors: Vec<String>,
+    // Disabled through the marker mechanism, SMAPI still loads these
+    pub still_loaded: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BulkToggleResult {
+    // Folder names after toggling
+    pub changed: Vec<String>,
+    // Disabled through the marker mechanism, SMAPI still loads these
+    pub still_loaded: Vec<String>,
+}
+
+impl BulkToggleResult {
+    fn record(&mut self, folder_name: String, enabled: bool) {
+        if !enabled && is_loaded_folder(&folder_name) {
+            warn!("{} was disabled with a marker, SMAPI will still load it", folder_name);
+            self.still_loaded.push(folder_name.clone());
+        }
+        self.changed.push(folder_name);
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ModImportResult {
+    // Folder names in the Mods directory
+    pub imported: Vec<String>,
+    // Source folders already installed, or without a valid manifest
+    pub skipped: Vec<String>,
+    // Source folders whose name is taken by a different folder in the Mods directory
+    pub conflicted: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct NexusRateLimit {
+    pub hourly_remaining: Option<u32>,
+    pub hourly_reset: Option<String>,
+    pub exhausted: bool,
+}
+
+impl NexusRateLimit {
+    fn error(&self) -> AppError {
+        let message = match &self.hourly_reset {
+            Some(reset) => format!("Nexus API rate limit reached, it resets at {}", reset),
+            None => "Nexus API rate limit reached, try again later".to_string(),
+        };
+        
+        AppError::RateLimited {
+            reset: self.hourly_reset.clone(),
+            message,
+        }
+    }
+    
+    fn exhausted_error(&self) -> Option<AppError> {
+        self.exhausted.then(|| self.error())
+    }
+}
+
+// Update key lookups made during one batch, so each remote resource is fetched once
+type UpdateKeyLookups = Mutex<HashMap<String, std::sync::Arc<tokio::sync::OnceCell<Result<UpdateInfo, AppError>>>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompatibilityStatus {
+    Ok,
+    Broken,
+    Obsolete,
+    #[serde(alias = "unofficial")]
+    UnofficialUpdateAvailable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModCompatibility {
+    pub unique_id: String,
+    pub status: CompatibilityStatus,
+    pub summary: Option<String>,
+    pub alternative_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CompatibilityEntry {
+    #[serde(alias = "id")]
+    pub ids: Vec<String>,
+    pub status: CompatibilityStatus,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default, alias = "unofficialUpdateUrl", alias = "alternativeUrl")]
+    pub alternative_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SmapiApiRequest {
+    pub mods: Vec<SmapiApiMod>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    pub game_version: Option<String>,
+    pub platform: &'static str,
+    pub include_extended_metadata: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SmapiApiMod {
+    pub id: String,
+    pub update_keys: Vec<String>,
+    pub installed_version: String,
+    pub is_broken: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SmapiApiResult {
+    pub id: String,
+    #[serde(default)]
+    pub suggested_update: Option<SmapiApiUpdate>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmapiApiUpdate {
+    pub version: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CompatibilityCache {
+    pub fetched_at: u64,
+    pub entries: Vec<CompatibilityEntry>,
+}
+
+// Settings the shared HTTP client was built from
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HttpClientConfig {
+    pub timeout_secs: u64,
+    pub proxy_url: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UpdateCache {
+    pub entries: HashMap<String, CachedUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedUpdate {
+    pub latest_version: String,
+    pub download_url: Option<String>,
+    pub checked_at: u64,
+}
+
+#[tauri::command]
+fn detect_stardew_valley() -> Result<StardewInfo, AppError> {
+    // A path saved by the user or a previous detection wins over the default locations
+    let settings = get_settings().unwrap_or_default();
+    if let Some(game_path) = settings.game_path.filter(|path| is_stardew_directory(path)) {
+        let mods_path = settings.mods_path
+            .filter(|path| path.is_dir())
+            .or_else(|| find_mods_path(&game_path));
+        
+        return Ok(StardewInfo {
+            warning: install_warning(&game_path),
+            game_path: Some(game_path),
+            mods_path,
+            found: true,
+        });
+    }
+    
+    let possible_paths = get_stardew_paths();
+    
+    if possible_paths.is_empty() {
+        return Err(AppError::NotFound("No potential Stardew Valley installation paths found for this operating system".to_string()));
+    }
+    
+    
+    for path in possible_paths {
+        if is_stardew_directory(&path) {
+            let mods_path = find_mods_path(&path);
+            
+            // Remember the detected location for the next launch
+            let mut settings = get_settings().unwrap_or_default();
+            settings.game_path = Some(path.clone());
+            settings.mods_path = mods_path.clone();
+            if let Err(e) = save_settings(settings) {
+                error!("Error saving detected paths: {}", e);
+            }
+            
+            return Ok(StardewInfo {
+                warning: install_warning(&path),
+                game_path: Some(path),
+                mods_path,
+                found: true,
+            });
+        }
+    }
+    
+    Ok(StardewInfo {
+        game_path: None,
+        mods_path: None,
+        found: false,
+        warning: None,
+    })
+}
+
+#[tauri::command]
+fn set_manual_game_path(path: String) -> Result<StardewInfo, AppError> {
+    let game_path = PathBuf::from(path.trim());
+    
+    if !game_path.is_dir() {
+        return Err(AppError::NotFound(format!("Folder does not exist: {}", game_path.display())));
+    }
+    
+    if !is_stardew_directory(&game_path) {
+        return Err(AppError::Invalid(format!("{} doesn't look like a Stardew Valley installation", game_path.display())));
+    }
+    
+    let mods_path = find_mods_path(&game_path);
+    
+    let mut settings = get_settings().unwrap_or_default();
+    settings.game_path = Some(game_path.clone());
+    settings.mods_path = mods_path.clone();
+    save_settings(settings)?;
+    
+    Ok(StardewInfo {
+        warning: install_warning(&game_path),
+        game_path: Some(game_path),
+        mods_path,
+        found: true,
+    })
+}
+
+#[tauri::command]
+fn validate_mods_path(path: String) -> Result<ModsPathValidation, AppError> {
+    let reason = mods_path_problem(Path::new(path.trim()));
+    if let Some(reason) = &reason {
+        info!("Rejected mods path {}: {}", path, reason);
+    }
+    
+    Ok(ModsPathValidation {
+        valid: reason.is_none(),
+        reason,
+    })
+}
+
+#[tauri::command]
+fn scan_mods(mods_path: String, include_sizes: Option<bool>, include_staged: Option<bool>, include_fingerprints: Option<bool>) -> Result<Vec<ModInfo>, AppError> {
+    let include_fingerprints = include_fingerprints.unwrap_or(false);
+    let mut mods = Vec::new();
+    scan_mods_with(Path::new(&mods_path), include_sizes.unwrap_or(false), |mut mod_info| {
+        if include_fingerprints && !mod_info.needs_folder {
+            mod_info.fingerprint = folder_fingerprint(&Path::new(&mods_path).join(&mod_info.folder_name)).ok();
+        }
+        mods.push(mod_info);
+    })?;
+    flag_duplicate_ids(&mut mods);
+    
+    if include_staged.unwrap_or(false) {
+        let staging_dir = staging_dir(Path::new(&mods_path))?;
+        if staging_dir.is_dir() {
+            scan_mods_with(&staging_dir, include_sizes.unwrap_or(false), |mut mod_info| {
+                if include_fingerprints {
+                    mod_info.fingerprint = folder_fingerprint(&staging_dir.join(&mod_info.folder_name)).ok();
+                }
+                mod_info.enabled = false;
+                mod_info.loaded = false;
+                mod_info.staged = true;
+                mods.push(mod_info);
+            })?;
+        }
+    }
+    
+    sort_mods(&mut mods);
+    Ok(mods)
+}
+
+#[tauri::command]
+fn mod_fingerprint(mods_path: String, folder_name: String) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    folder_fingerprint(&mod_path)
+}
+
+// Hashes file metadata rather than contents, cheap enough to run on every scan
+fn folder_fingerprint(mod_path: &Path) -> Result<String, AppError> {
+    let mut files = HashSet::new();
+    collect_relative_files(mod_path, mod_path, &mut files)?;
+    let mut files: Vec<PathBuf> = files.into_iter().collect();
+    files.sort();
+    
+    let mut hasher = md5::Context::new();
+    for relative_path in files {
+        let metadata = fs::metadata(mod_path.join(&relative_path))
+            .map_err(|e| AppError::Io(format!("Failed to read {}: {}", relative_path.display(), e)))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_millis());
+        
+        // Normalised separators keep fingerprints stable across platforms
+        let line = format!("{}\t{}\t{}\n", relative_path.to_string_lossy().replace('\\', "/"), metadata.len(), modified);
+        hasher.consume(line.as_bytes());
+    }
+    
+    Ok(format!("{:x}", hasher.compute()))
+}
+
+#[tauri::command]
+fn find_mod_containing_file(mods_path: String, filename: String) -> Result<Vec<FileMatch>, AppError> {
+    // Only the name is compared, "Mods/foo.xnb" from a log line works as well
+    let filename = filename.trim().replace('\\', "/");
+    let filename = filename.rsplit('/').next().unwrap_or_default().to_lowercase();
+    if filename.is_empty() {
+        return Err(AppError::Invalid("No file name given".to_string()));
+    }
+    
+    let mods_dir = Path::new(&mods_path);
+    let mut matches = Vec::new();
+    for mod_info in scan_mods(mods_path.clone(), None, None, None)? {
+        if mod_info.needs_folder {
+            continue;
+        }
+        
+        let mod_path = mods_dir.join(&mod_info.folder_name);
+        let mut found = Vec::new();
+        collect_files_named(&mod_path, &mod_path, &filename, 0, &mut found);
+        found.sort();
+        matches.extend(found.into_iter().map(|relative_path| FileMatch {
+            folder_name: mod_info.folder_name.clone(),
+            mod_name: mod_info.name.clone(),
+            relative_path,
+        }));
+    }
+    
+    info!("Found {} in {} places", filename, matches.len());
+    Ok(matches)
+}
+
+fn collect_files_named(base: &Path, dir: &Path, filename: &str, depth: usize, found: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    
+    for entry in entries.flatten() {
+        match entry.path().symlink_metadata() {
+            Ok(metadata) if metadata.is_dir() && depth < MAX_FILE_SEARCH_DEPTH => {
+                collect_files_named(base, &entry.path(), filename, depth + 1, found);
+            }
+            Ok(metadata) if metadata.is_file() && entry.file_name().to_string_lossy().to_lowercase() == filename => {
+                if let Ok(relative_path) = entry.path().strip_prefix(base) {
+                    found.push(relative_path.to_string_lossy().replace('\\', "/"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[tauri::command]
+fn search_mods(mods_path: String, query: String) -> Result<Vec<ModInfo>, AppError> {
+    // "author:name" or "name:foo" limits the match to one field
+    let query = query.trim();
+    let (field, term) = match query.split_once(':') {
+        Some((field, term)) if ["author", "name", "description"].contains(&field.trim().to_lowercase().as_str()) => {
+            (Some(field.trim().to_lowercase()), term.trim().to_lowercase())
+        }
+        _ => (None, query.to_lowercase()),
+    };
+    
+    let matches = |mod_info: &ModInfo| {
+        let fields = [("name", &mod_info.name), ("author", &mod_info.author), ("description", &mod_info.description)];
+        fields
+            .iter()
+            .filter(|(name, _)| field.as_deref().is_none_or(|field| field == *name))
+            .any(|(_, value)| value.to_lowercase().contains(&term))
+    };
+    
+    let mut mods = Vec::new();
+    scan_mods_with(Path::new(&mods_path), false, |mod_info| {
+        if matches(&mod_info) {
+            mods.push(mod_info);
+        }
+    })?;
+    sort_mods(&mut mods);
+    Ok(mods)
+}
+
+#[tauri::command]
+async fn scan_mods_streaming(window: tauri::Window, mods_path: String, include_sizes: Option<bool>) -> Result<usize, AppError> {
+    // Scanning thousands of folders takes a while, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut mods = Vec::new();
+        scan_mods_with(Path::new(&mods_path), include_sizes.unwrap_or(false), |mod_info| {
+            let _ = window.emit("mod-scanned", &mod_info);
+            mods.push(mod_info);
+        })?;
+        
+        // Duplicates are only known once everything is scanned, send those mods again
+        for index in flag_duplicate_ids(&mut mods) {
+            let _ = window.emit("mod-scanned", &mods[index]);
+        }
+        
+        // Mods arrive in directory order, the final event carries the sorted order
+        sort_mods(&mut mods);
+        let complete = ScanComplete {
+            count: mods.len(),
+            order: mods.into_iter().map(|mod_info| mod_info.folder_name).collect(),
+        };
+        let _ = window.emit("scan-complete", &complete);
+        
+        info!("Streamed {} mods from {}", complete.count, mods_path);
+        Ok(complete.count)
+    })
+    .await
+    .map_err(|e| AppError::Io(format!("Mod scan failed: {}", e)))?
+}
+
+fn scan_mods_with(path: &Path, include_sizes: bool, mut on_mod: impl FnMut(ModInfo)) -> Result<(), AppError> {
+    if !path.exists() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", path.display())));
+    }
+    
+    if !path.is_dir() {
+        return Err(AppError::Invalid(format!("Path is not a directory: {}", path.display())));
+    }
+    
+    let disabled_ids: HashSet<String> = get_settings().map(|settings| settings.disabled_mod_ids.into_iter().collect()).unwrap_or_default();
+    let mut on_mod = |mut mod_info: ModInfo| {
+        // The size of a loose mod would be the whole Mods folder
+        if !mod_info.needs_folder {
+            fill_mod_size(path, &mut mod_info, include_sizes);
+        }
+        
+        // Mods disabled without renaming their folder
+        if mod_info.enabled
+            && (disabled_ids.contains(&mod_info.unique_id.to_lowercase()) || path.join(&mod_info.folder_name).join(DISABLED_MARKER).is_file())
+        {
+            mod_info.enabled = false;
+        }
+        on_mod(mod_info);
+    };
+    
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries {
+                match entry {
+                    Ok(entry) => {
+                        if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+                            collect_mod_folder(&entry.path(), entry.file_name().to_string_lossy().to_string(), 0, &mut on_mod);
+                        }
+                    },
+                    Err(e) => {
+                        error!("Error reading directory entry: {}", e);
+                    }
+                }
+            }
+        },
+        Err(e) => {
+            return Err(AppError::Io(format!("Failed to read mods directory: {}", e)));
+        }
+    }
+    
+    // A mod extracted without its folder leaves its manifest at the root
+    if find_manifest(path).is_some() {
+        if let Some(mut mod_info) = parse_mod_folder(path) {
+            warn!("Found a loose manifest at the root of {}", path.display());
+            mod_info.folder_name = String::new();
+            mod_info.enabled = true;
+            mod_info.needs_folder = true;
+            on_mod(mod_info);
+        }
+    }
+    
+    Ok(())
+}
+
+fn sort_mods(mods: &mut [ModInfo]) {
+    // Sort mods alphabetically by name, ignoring [CP] prefix
+    mods.sort_by(|a, b| {
+        let clean_name_a = a.name.strip_prefix("[CP] ").unwrap_or(&a.name).to_lowercase();
+        let clean_name_b = b.name.strip_prefix("[CP] ").unwrap_or(&b.name).to_lowercase();
+        clean_name_a.cmp(&clean_name_b)
+    });
+}
+
+// Walking every file is slow on big content packs, so sizes are opt-in and
+// otherwise filled from the last time they were computed
+fn fill_mod_size(mods_dir: &Path, mod_info: &mut ModInfo, include_sizes: bool) {
+    let cache_key = mods_dir.join(enabled_folder_name(&mod_info.folder_name));
+    if include_sizes {
+        mod_info.size_bytes = dir_size(&mods_dir.join(&mod_info.folder_name));
+        MOD_SIZE_CACHE.lock().unwrap().insert(cache_key, mod_info.size_bytes);
+    } else if let Some(size_bytes) = MOD_SIZE_CACHE.lock().unwrap().get(&cache_key) {
+        mod_info.size_bytes = *size_bytes;
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+    
+    entries
+        .flatten()
+        .map(|entry| match entry.path().symlink_metadata() {
+            // Don't follow symlinks, they could point back up the tree
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) if metadata.is_file() => metadata.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn collect_mod_folder(path: &Path, relative_name: String, depth: usize, on_mod: &mut dyn FnMut(ModInfo)) {
+    if let Some(mut mod_info) = parse_mod_folder(path) {
+        mod_info.folder_name = relative_name;
+        on_mod(mod_info);
+        return;
+    }
+    
+    // SMAPI also loads mods grouped in plain folders, e.g. Mods/UI/SomeMod
+    let folder_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    if depth >= MAX_MOD_GROUP_DEPTH || folder_name.starts_with('.') || folder_name.starts_with("__") || folder_name.ends_with(BACKUP_SUFFIX) {
+        return;
+    }
+    
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+                let child_name = format!("{}/{}", relative_name, entry.file_name().to_string_lossy());
+                collect_mod_folder(&entry.path(), child_name, depth + 1, on_mod);
+            }
+        }
+    }
+}
+
+#[tauri::command]
+async fn check_mod_updates(mods: Vec<ModInfo>, force: Option<bool>) -> Result<HashMap<String, UpdateInfo>, AppError> {
+    use futures::stream::{self, StreamExt};
+    
+    let mut updates = HashMap::new();
+    let force = force.unwrap_or(false);
+    let settings = get_settings().unwrap_or_default();
+    let cache = Mutex::new(load_update_cache());
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    let lookups = UpdateKeyLookups::default();
+    
+    // Check a bounded number of mods at once to stay polite to the APIs
+    let results: Vec<(ModInfo, Result<UpdateInfo, AppError>)> = stream::iter(mods)
+        .map(|mod_info| {
+            let settings = &settings;
+            let cache = &cache;
+            let rate_limit = &rate_limit;
+            let lookups = &lookups;
+            async move {
+                let result = check_single_mod_update(&mod_info, settings, cache, rate_limit, lookups, force).await;
+                (mod_info, result)
+            }
+        })
+        .buffer_unordered(UPDATE_CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    
+    for (mod_info, result) in results {
+        match result {
+            Ok(update_info) => {
+                updates.insert(mod_info.folder_name, update_info);
+            }
+            // Every Nexus mod after the limit was hit ends up here, each one carries the reset time
+            Err(AppError::RateLimited { reset, message }) => {
+                warn!("Rate limited while checking {}: {}", mod_info.name, message);
+                updates.insert(mod_info.folder_name, UpdateInfo {
+                    failed_checks: vec![message],
+                    rate_limit_reset: reset,
+                    ..UpdateInfo::unchecked(&mod_info.version, UpdateStatus::RateLimited)
+                });
+            }
+            Err(e) => {
+                error!("Error checking updates for {}: {}", mod_info.name, e);
+                // Keep the mod in the results so the UI can show that its check failed
+                updates.insert(mod_info.folder_name, UpdateInfo::unchecked(&mod_info.version, UpdateStatus::Error));
+            }
+        }
+    }
+    
+    if let Err(e) = save_update_cache(&cache.into_inner().unwrap()) {
+        error!("Error saving update cache: {}", e);
+    }
+    
+    Ok(updates)
+}
+
+#[tauri::command]
+async fn check_updates_via_smapi_api(mods: Vec<ModInfo>) -> Result<HashMap<String, UpdateInfo>, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let mut updates = HashMap::new();
+    // Lowercased unique ID -> (folder name, installed version, has update keys)
+    let mut folders_by_id: HashMap<String, Vec<(String, String, bool)>> = HashMap::new();
+    let mut api_mods = Vec::new();
+    
+    for mod_info in mods {
+        // Same precedence as check_single_mod_update
+        let pinned = settings.pinned_versions.get(&enabled_folder_name(&mod_info.folder_name));
+        let status = if pinned.is_some_and(|version| normalize_version(version) == normalize_version(&mod_info.version)) {
+            Some(UpdateStatus::Pinned)
+        } else if settings.offline {
+            Some(UpdateStatus::Offline)
+        } else if mod_info.unique_id.is_empty() {
+            // The API looks mods up by unique ID
+            Some(UpdateStatus::NoUpdateKey)
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            updates.insert(mod_info.folder_name, UpdateInfo::unchecked(&mod_info.version, status));
+            continue;
+        }
+        
+        let id = mod_info.unique_id.to_lowercase();
+        if !folders_by_id.contains_key(&id) {
+            api_mods.push(SmapiApiMod {
+                id: mod_info.unique_id.clone(),
+                update_keys: mod_info.update_keys.clone(),
+                installed_version: mod_info.version.clone(),
+                is_broken: mod_info.invalid.is_some(),
+            });
+        }
+        let has_update_keys = !mod_info.update_keys.is_empty();
+        folders_by_id.entry(id).or_default().push((mod_info.folder_name, mod_info.version, has_update_keys));
+    }
+    
+    if api_mods.is_empty() {
+        return Ok(updates);
+    }
+    
+    let platform = if cfg!(target_os = "windows") {
+        "Windows"
+    } else if cfg!(target_os = "macos") {
+        "Mac"
+    } else {
+        "Linux"
+    };
+    let body = SmapiApiRequest {
+        mods: api_mods,
+        // The API tailors suggestions to the installed SMAPI, left out when no install can be found
+        api_version: smapi_version_from_log().or_else(|| {
+            let game_path = settings.game_path.as_ref()?;
+            detect_smapi(game_path.to_string_lossy().to_string()).ok()?.version
+        }),
+        game_version: game_version_from_log(),
+        platform,
+        include_extended_metadata: false,
+    };
+    
+    let client = http_client(&settings)?;
+    let request = client
+        .post(SMAPI_API_URL)
+        .json(&body)
+        .timeout(request_timeout(&settings));
+    let response = send_with_retry(request, settings.max_retries)
+        .await
+        .map_err(|e| network_error(&settings, "Failed to reach the SMAPI update API", e))?;
+    
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!("SMAPI update API returned status: {}", response.status())));
+    }
+    
+    let results: Vec<SmapiApiResult> = response
+        .json()
+        .await
+        .map_err(|e| AppError::Parse(format!("Failed to parse SMAPI update API response: {}", e)))?;
+    
+    for result in results {
+        let Some(folders) = folders_by_id.remove(&result.id.to_lowercase()) else {
+            continue;
+        };
+        
+        for (folder_name, version, has_update_keys) in folders {
+            // No suggested update means SMAPI found nothing newer than the installed version
+            let update_info = match &result.suggested_update {
+                // The suggestion is for the first folder sent, a duplicate may already be at or past it
+                Some(update) if !version_compare(&version, &update.version) => UpdateInfo {
+                    checked_via: "SMAPI API".to_string(),
+                    failed_checks: result.errors.clone(),
+                    ..UpdateInfo::unchecked(&version, UpdateStatus::UpToDate)
+                },
+                Some(update) => UpdateInfo {
+                    current_version: version,
+                    latest_version: update.version.clone(),
+                    update_available: true,
+                    download_url: update.url.clone(),
+                    update_status: UpdateStatus::Available,
+                    checked_via: "SMAPI API".to_string(),
+                    failed_checks: result.errors.clone(),
+                    rate_limit_reset: None,
+                },
+                // Without update keys only an entry in SMAPI's compatibility list could have suggested something
+                None if !has_update_keys => UpdateInfo::unchecked(&version, UpdateStatus::NoUpdateKey),
+                None if !result.errors.is_empty() => {
+                    debug!("SMAPI API could not check {}: {:?}", folder_name, result.errors);
+                    UpdateInfo {
+                        failed_checks: result.errors.clone(),
+                        ..UpdateInfo::unchecked(&version, UpdateStatus::Error)
+                    }
+                }
+                None => UpdateInfo {
+                    checked_via: "SMAPI API".to_string(),
+                    ..UpdateInfo::unchecked(&version, UpdateStatus::UpToDate)
+                },
+            };
+            updates.insert(folder_name, update_info);
+        }
+    }
+    
+    // Mods missing from the response weren't checked at all
+    for (folder_name, version, _) in folders_by_id.into_values().flatten() {
+        updates.insert(folder_name, UpdateInfo::unchecked(&version, UpdateStatus::Error));
+    }
+    
+    info!("Checked {} mods through the SMAPI update API", updates.len());
+    Ok(updates)
+}
+
+#[tauri::command]
+fn check_dependencies(mods: Vec<ModInfo>) -> Result<HashMap<String, Vec<String>>, AppError> {
+    // Mods SMAPI doesn't load can't satisfy a dependency, marker-disabled ones still do
+    let installed: HashSet<String> = mods
+        .iter()
+        .filter(|mod_info| mod_info.loaded && !mod_info.unique_id.is_empty())
+        .map(|mod_info| mod_info.unique_id.to_lowercase())
+        .collect();
+    
+    let mut missing_dependencies = HashMap::new();
+    
+    for mod_info in mods.iter().filter(|mod_info| mod_info.loaded) {
+        let mut missing: Vec<String> = mod_info.dependencies
+            .iter()
+            .filter(|dependency| dependency.is_required && !installed.contains(&dependency.unique_id.to_lowercase()))
+            .map(|dependency| dependency.unique_id.clone())
+            .collect();
+        
+        // A content pack is useless without the mod that loads it
+        if let Some(target) = &mod_info.content_pack_for {
+            if !installed.contains(&target.to_lowercase()) && !missing.iter().any(|id| id.eq_ignore_ascii_case(target)) {
+                missing.push(target.clone());
+            }
+        }
+        
+        if !missing.is_empty() {
+            info!("{} is missing dependencies: {:?}", mod_info.folder_name, missing);
+            missing_dependencies.insert(mod_info.folder_name.clone(), missing);
+        }
+    }
+    
+    Ok(missing_dependencies)
+}
+
+#[tauri::command]
+fn suggest_load_order(mods: Vec<ModInfo>) -> Result<LoadOrderSuggestion, AppError> {
+    // Only mods SMAPI will actually load take part
+    let mods: Vec<ModInfo> = mods.into_iter().filter(|mod_info| mod_info.loaded && !mod_info.unique_id.is_empty()).collect();
+    let index_by_id: HashMap<String, usize> = mods
+        .iter()
+        .enumerate()
+        .map(|(index, mod_info)| (mod_info.unique_id.to_lowercase(), index))
+        .collect();
+    
+    // Edges go from a mod to the installed mods it needs loaded first, optional ones included
+    let mut dependencies: Vec<Vec<usize>> = vec![Vec::new(); mods.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); mods.len()];
+    for (index, mod_info) in mods.iter().enumerate() {
+        let targets = mod_info.dependencies
+            .iter()
+            .map(|dependency| dependency.unique_id.as_str())
+            .chain(mod_info.content_pack_for.as_deref());
+        for target in targets {
+            if let Some(&target_index) = index_by_id.get(&target.to_lowercase()) {
+                if target_index != index && !dependencies[index].contains(&target_index) {
+                    dependencies[index].push(target_index);
+                    dependents[target_index].push(index);
+                }
+            }
+        }
+    }
+    
+    // Kahn's algorithm, ties broken by the load order hint and then by name so the result is stable
+    let sort_key = |index: usize| (mods[index].load_order.unwrap_or(0), mods[index].name.to_lowercase(), index);
+    let mut remaining: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut ready: std::collections::BTreeSet<(i64, String, usize)> = (0..mods.len())
+        .filter(|&index| remaining[index] == 0)
+        .map(sort_key)
+        .collect();
+    let mut order = Vec::with_capacity(mods.len());
+    while let Some((_, _, index)) = ready.pop_first() {
+        order.push(index);
+        for &dependent in &dependents[index] {
+            remaining[dependent] -= 1;
+            if remaining[dependent] == 0 {
+                ready.insert(sort_key(dependent));
+            }
+        }
+    }
+    
+    // Whatever is left is in a cycle or depends on one
+    let mut cycles = Vec::new();
+    let mut unsorted: Vec<usize> = (0..mods.len()).filter(|&index| remaining[index] > 0).collect();
+    if !unsorted.is_empty() {
+        let mut visited = vec![false; mods.len()];
+        let mut seen_cycles = HashSet::new();
+        for &start in &unsorted {
+            find_dependency_cycles(start, &dependencies, &mut visited, &mut Vec::new(), &mut |cycle| {
+                let mut members = cycle.to_vec();
+                members.sort_unstable();
+                if seen_cycles.insert(members) {
+                    cycles.push(cycle.iter().map(|&index| mods[index].folder_name.clone()).collect::<Vec<String>>());
+                }
+            });
+        }
+        warn!("Found {} dependency cycles, {} mods can't be ordered", cycles.len(), unsorted.len());
+        
+        unsorted.sort_by_key(|&index| sort_key(index));
+        order.extend(unsorted);
+    }
+    
+    Ok(LoadOrderSuggestion {
+        order: order.into_iter().map(|index| mods[index].folder_name.clone()).collect(),
+        cycles,
+    })
+}
+
+fn find_dependency_cycles(index: usize, dependencies: &[Vec<usize>], visited: &mut [bool], stack: &mut Vec<usize>, on_cycle: &mut dyn FnMut(&[usize])) {
+    if let Some(position) = stack.iter().position(|&entry| entry == index) {
+        on_cycle(&stack[position..]);
+        return;
+    }
+    if visited[index] {
+        return;
+    }
+    
+    visited[index] = true;
+    stack.push(index);
+    for &dependency in &dependencies[index] {
+        find_dependency_cycles(dependency, dependencies, visited, stack, on_cycle);
+    }
+    stack.pop();
+}
+
+#[tauri::command]
+fn find_missing_frameworks(mods: Vec<ModInfo>) -> Result<HashMap<String, MissingFramework>, AppError> {
+    // Copies SMAPI skips still tell us the framework's name, a loaded copy wins over them
+    let mut installed: HashMap<String, &ModInfo> = HashMap::new();
+    for mod_info in mods.iter().filter(|mod_info| !mod_info.unique_id.is_empty()) {
+        installed
+            .entry(mod_info.unique_id.to_lowercase())
+            .and_modify(|existing| {
+                if !existing.loaded {
+                    *existing = mod_info;
+                }
+            })
+            .or_insert(mod_info);
+    }
+    
+    let mut missing = HashMap::new();
+    for mod_info in mods.iter().filter(|mod_info| mod_info.loaded) {
+        let Some(framework_id) = &mod_info.content_pack_for else {
+            continue;
+        };
+        
+        let framework = installed.get(&framework_id.to_lowercase());
+        if framework.is_some_and(|framework| framework.loaded) {
+            continue;
+        }
+        
+        let name = framework.map(|framework| framework.name.clone()).or_else(|| {
+            KNOWN_FRAMEWORKS
+                .iter()
+                .find(|(id, _)| id.eq_ignore_ascii_case(framework_id))
+                .map(|(_, name)| name.to_string())
+        });
+        info!("{} is a content pack for {}, which is not {}", mod_info.folder_name, framework_id, if framework.is_some() { "enabled" } else { "installed" });
+        missing.insert(mod_info.folder_name.clone(), MissingFramework {
+            unique_id: framework_id.clone(),
+            name,
+            disabled: framework.is_some(),
+        });
+    }
+    
+    Ok(missing)
+}
+
+#[tauri::command]
+fn mods_depending_on(unique_id: String, all_mods: Vec<ModInfo>) -> Result<Vec<String>, AppError> {
+    let unique_id = unique_id.trim();
+    
+    // Content packs can't load without their framework either
+    let dependents = all_mods
+        .into_iter()
+        .filter(|mod_info| {
+            mod_info.dependencies.iter().any(|dependency| dependency.is_required && dependency.unique_id.eq_ignore_ascii_case(unique_id))
+                || mod_info.content_pack_for.as_deref().is_some_and(|target| target.eq_ignore_ascii_case(unique_id))
+        })
+        .map(|mod_info| mod_info.folder_name)
+        .collect();
+    
+    Ok(dependents)
+}
+
+#[tauri::command]
+fn find_duplicate_mods(mods: Vec<ModInfo>) -> Result<Vec<DuplicateModGroup>, AppError> {
+    // SMAPI refuses to load any mod whose UniqueID is installed more than once
+    let mut groups: Vec<DuplicateModGroup> = Vec::new();
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+    
+    for mod_info in &mods {
+        let unique_id = mod_info.unique_id.trim();
+        if unique_id.is_empty() {
+            continue;
+        }
+        
+        let index = *group_index.entry(unique_id.to_lowercase()).or_insert_with(|| {
+            groups.push(DuplicateModGroup {
+                unique_id: unique_id.to_string(),
+                folder_names: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[index].folder_names.push(mod_info.folder_name.clone());
+    }
+    
+    groups.retain(|group| group.folder_names.len() > 1);
+    for group in &groups {
+        info!("Duplicate mod {} found in: {:?}", group.unique_id, group.folder_names);
+    }
+    
+    Ok(groups)
+}
+
+// Best effort only: patches are read without evaluating tokens or "When" conditions,
+// so two Loads that never apply together are still reported, and targets built from
+// tokens are compared as written. Only Load is checked since it's the exclusive action.
+#[tauri::command]
+fn find_content_conflicts(mods_path: String, mods: Vec<ModInfo>) -> Result<Vec<ContentConflict>, AppError> {
+    let mods_dir = Path::new(&mods_path);
+    let mut loads_by_target: HashMap<String, (String, Vec<String>)> = HashMap::new();
+    
+    // Mods without a content.json simply contribute nothing
+    for mod_info in mods.iter().filter(|mod_info| mod_info.loaded) {
+        let mod_path = mods_dir.join(&mod_info.folder_name);
+        let content_dir = find_manifest(&mod_path)
+            .and_then(|manifest_path| manifest_path.parent().map(Path::to_path_buf))
+            .unwrap_or(mod_path);
+        
+        let mut targets = HashSet::new();
+        collect_load_targets(&content_dir, Path::new("content.json"), 0, &mut targets);
+        
+        for target in targets {
+            let entry = loads_by_target.entry(target.to_lowercase()).or_insert_with(|| (target.clone(), Vec::new()));
+            entry.1.push(mod_info.folder_name.clone());
+        }
+    }
+    
+    let mut conflicts: Vec<ContentConflict> = loads_by_target
+        .into_values()
+        .filter(|(_, folder_names)| folder_names.len() > 1)
+        .map(|(target, folder_names)| ContentConflict { target, folder_names })
+        .collect();
+    conflicts.sort_by(|a, b| a.target.cmp(&b.target));
+    
+    for conflict in &conflicts {
+        info!("{} is loaded by several mods: {:?}", conflict.target, conflict.folder_names);
+    }
+    
+    Ok(conflicts)
+}
+
+// Reads a Content Patcher file and the files it includes, collecting the targets of Load patches
+fn collect_load_targets(content_dir: &Path, file: &Path, depth: usize, targets: &mut HashSet<String>) {
+    // Includes can point at each other, a few levels is plenty for real packs
+    if depth > 3 {
+        return;
+    }
+    
+    let Ok(content) = fs::read_to_string(content_dir.join(file)) else {
+        return;
+    };
+    let Ok(json) = parse_manifest_as::<serde_json::Value>(&content) else {
+        warn!("Could not parse {}", content_dir.join(file).display());
+        return;
+    };
+    let Some(changes) = json.get("Changes").and_then(|changes| changes.as_array()) else {
+        return;
+    };
+    
+    // Field names are case-insensitive in Content Patcher
+    let field = |patch: &serde_json::Value, name: &str| -> Option<String> {
+        patch.as_object()?.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).and_then(|(_, value)| value.as_str().map(str::to_string))
+    };
+    
+    for patch in changes {
+        let action = field(patch, "Action").unwrap_or_default();
+        if action.eq_ignore_ascii_case("Load") {
+            // A single patch can target several comma-separated assets
+            if let Some(target) = field(patch, "Target") {
+                targets.extend(target.split(',').map(|t| t.trim().replace('\\', "/")).filter(|t| !t.is_empty()));
+            }
+        } else if action.eq_ignore_ascii_case("Include") {
+            if let Some(from_file) = field(patch, "FromFile") {
+                for included in from_file.split(',').map(str::trim).filter(|f| !f.is_empty() && !f.contains("..")) {
+                    collect_load_targets(content_dir, Path::new(included), depth + 1, targets);
+                }
+            }
+        }
+    }
+}
+
+#[tauri::command]
+fn preflight_check(mods_path: String) -> Result<PreflightReport, AppError> {
+    let mods = scan_mods(mods_path.clone(), None, None, None)?;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let issue = |folder_name: &str, message: String| PreflightIssue { folder_name: Some(folder_name.to_string()), message };
+    
+    // The Mods folder lives inside the game folder, that's where SMAPI is installed
+    let settings = get_settings().unwrap_or_default();
+    let game_path = settings.game_path.or_else(|| Path::new(&mods_path).parent().map(Path::to_path_buf));
+    let smapi_version = match game_path.map(|game_path| detect_smapi(game_path.to_string_lossy().to_string())) {
+        Some(Ok(smapi)) if smapi.installed => smapi.version,
+        _ => {
+            errors.push(PreflightIssue { folder_name: None, message: "SMAPI is not installed, no mods will load".to_string() });
+            None
+        }
+    };
+    
+    // Marker-disabled mods are still loaded by SMAPI, so they're checked too
+    let loaded: Vec<&ModInfo> = mods.iter().filter(|mod_info| mod_info.loaded).collect();
+    let versions: HashMap<String, &str> = loaded
+        .iter()
+        .filter(|mod_info| !mod_info.unique_id.is_empty())
+        .map(|mod_info| (mod_info.unique_id.to_lowercase(), mod_info.version.as_str()))
+        .collect();
+    
+    for mod_info in &loaded {
+        // Covers broken manifests and duplicate unique IDs, SMAPI skips both
+        if let Some(reason) = &mod_info.invalid {
+            errors.push(issue(&mod_info.folder_name, reason.clone()));
+        }
+        if !mod_info.enabled {
+            warnings.push(issue(&mod_info.folder_name, MARKER_STILL_LOADED.to_string()));
+        }
+        if mod_info.needs_folder {
+            warnings.push(issue(&mod_info.folder_name, "Mod files are loose in the Mods folder, move them into their own folder".to_string()));
+        }
+        
+        if let (Some(required), Some(installed)) = (&mod_info.minimum_api_version, &smapi_version) {
+            if version_compare(installed, required) {
+                errors.push(issue(&mod_info.folder_name, format!("Requires SMAPI {} or later (installed: {})", required, installed)));
+            }
+        }
+        
+        for dependency in &mod_info.dependencies {
+            let Some(installed) = versions.get(&dependency.unique_id.to_lowercase()) else {
+                if !dependency.is_required {
+                    warnings.push(issue(&mod_info.folder_name, format!("Optional dependency {} is not installed", dependency.unique_id)));
+                }
+                continue;
+            };
+            if let Some(minimum) = &dependency.minimum_version {
+                if version_compare(installed, minimum) {
+                    let message = format!("Requires {} {} or later (installed: {})", dependency.unique_id, minimum, installed);
+                    if dependency.is_required {
+                        errors.push(issue(&mod_info.folder_name, message));
+                    } else {
+                        warnings.push(issue(&mod_info.folder_name, message));
+                    }
+                }
+            }
+        }
+    }
+    
+    let mod_count = mods.len();
+    let enabled_count = loaded.len();
+    for (folder_name, missing) in check_dependencies(mods)? {
+        for unique_id in missing {
+            errors.push(issue(&folder_name, format!("Missing required dependency {}", unique_id)));
+        }
+    }
+    
+    errors.sort_by(|a, b| a.folder_name.cmp(&b.folder_name));
+    warnings.sort_by(|a, b| a.folder_name.cmp(&b.folder_name));
+    info!("Preflight check: {} errors, {} warnings across {} loaded mods", errors.len(), warnings.len(), enabled_count);
+    
+    Ok(PreflightReport {
+        mod_count,
+        enabled_count,
+        smapi_version,
+        errors,
+        warnings,
+    })
+}
+
+#[tauri::command]
+fn export_mod_list(mods: Vec<ModInfo>, output_path: String, include_markdown: Option<bool>) -> Result<String, AppError> {
+    let export = ModListExport {
+        format_version: MOD_LIST_FORMAT_VERSION,
+        mods: mods
+            .iter()
+            .map(|mod_info| ExportedMod {
+                name: mod_info.name.clone(),
+                version: mod_info.version.clone(),
+                author: mod_info.author.clone(),
+                unique_id: mod_info.unique_id.clone(),
+                update_keys: mod_info.update_keys.clone(),
+            })
+            .collect(),
+    };
+    
+    let output_path = PathBuf::from(output_path);
+    let json = serde_json::to_string_pretty(&export).map_err(|e| AppError::Parse(format!("Failed to serialize mod list: {}", e)))?;
+    fs::write(&output_path, json).map_err(|e| AppError::Io(format!("Failed to write mod list: {}", e)))?;
+    info!("Exported {} mods to {}", export.mods.len(), output_path.display());
+    
+    if include_markdown.unwrap_or(false) {
+        let markdown_path = output_path.with_extension("md");
+        fs::write(&markdown_path, mod_list_markdown(&export.mods))
+            .map_err(|e| AppError::Io(format!("Failed to write Markdown mod list: {}", e)))?;
+        info!("Exported Markdown mod list to {}", markdown_path.display());
+    }
+    
+    Ok(format!("Exported {} mods", export.mods.len()))
+}
+
+#[tauri::command]
+fn import_mod_list(path: String) -> Result<Vec<ImportedMod>, AppError> {
+    let content = fs::read_to_string(&path).map_err(|e| AppError::Io(format!("Failed to read mod list: {}", e)))?;
+    let export: ModListExport = serde_json::from_str(&content).map_err(|e| AppError::Parse(format!("Failed to parse mod list: {}", e)))?;
+    
+    if export.format_version > MOD_LIST_FORMAT_VERSION {
+        return Err(AppError::Invalid(format!("Unsupported mod list format version: {}", export.format_version)));
+    }
+    
+    Ok(export
+        .mods
+        .into_iter()
+        .map(|exported| {
+            let links = exported.update_keys.iter().filter_map(|key| update_key_url(key)).collect();
+            ImportedMod { info: exported, links }
+        })
+        .collect())
+}
+
+fn mod_list_markdown(mods: &[ExportedMod]) -> String {
+    let mut markdown = String::from("# Stardew Valley mod list\n\n| Name | Version | Author | Links |\n| --- | --- | --- | --- |\n");
+    for exported in mods {
+        let links: Vec<String> = exported.update_keys
+            .iter()
+            .filter_map(|key| update_key_url(key))
+            .map(|url| format!("[link]({})", url))
+            .collect();
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            exported.name.replace('|', "\\|"),
+            exported.version,
+            exported.author.replace('|', "\\|"),
+            links.join(" ")
+        ));
+    }
+    markdown
+}
+
+// Mod page for an update key, used when sharing mod lists
+fn update_key_url(update_key: &str) -> Option<String> {
+    let update_key = update_key.trim();
+    let key_lower = update_key.to_lowercase();
+    if key_lower.starts_with("http://") || key_lower.starts_with("https://") {
+        return Some(update_key.to_string());
+    }
+    
+    // The "@subkey" suffix only selects a file or tag, the page is the same
+    let (provider, id, _) = parse_update_key(update_key)?;
+    match provider.as_str() {
+        "nexus" => Some(format!("https://www.nexusmods.com/stardewvalley/mods/{}", id)),
+        "github" => Some(format!("https://github.com/{}", id)),
+        "chucklefish" => Some(format!("https://community.playstarbound.com/resources/{}", id)),
+        "moddrop" => Some(format!("https://www.moddrop.com/stardew-valley/mod/{}", id)),
+        _ => None,
+    }
+}
+
+#[tauri::command]
+async fn get_nexus_mod_details(mod_id: u32) -> Result<NexusModDetails, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let api_key = nexus_api_key(&settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    
+    nexus_api_get(&format!("games/stardewvalley/mods/{}", mod_id), api_key, &rate_limit, &settings)
+        .await
+        .map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound(format!("Mod {} not found on Nexus", mod_id)),
+            e => e,
+        })
+}
+
+#[tauri::command]
+async fn get_nexus_changelog(mod_id: u32) -> Result<HashMap<String, Vec<String>>, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let api_key = nexus_api_key(&settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    
+    let changelog: serde_json::Value = nexus_api_get(&format!("games/stardewvalley/mods/{}/changelogs.json", mod_id), api_key, &rate_limit, &settings)
+        .await
+        .map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound(format!("Mod {} not found on Nexus", mod_id)),
+            e => e,
+        })?;
+    
+    // Nexus returns an empty array instead of an object when there are no entries
+    match changelog {
+        serde_json::Value::Object(_) => serde_json::from_value(changelog)
+            .map_err(|e| AppError::Parse(format!("Failed to parse Nexus changelog: {}", e))),
+        _ => Ok(HashMap::new()),
+    }
+}
+
+#[tauri::command]
+async fn get_nexus_tracked_mods() -> Result<Vec<u32>, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let api_key = nexus_api_key(&settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    
+    let tracked: Vec<NexusTrackedMod> = nexus_api_get("user/tracked_mods.json", api_key, &rate_limit, &settings).await?;
+    
+    // Tracking is account wide, mods for other games come back too
+    let mut mod_ids: Vec<u32> = tracked
+        .into_iter()
+        .filter(|tracked_mod| tracked_mod.domain_name.eq_ignore_ascii_case("stardewvalley"))
+        .map(|tracked_mod| tracked_mod.mod_id)
+        .collect();
+    mod_ids.sort_unstable();
+    mod_ids.dedup();
+    
+    info!("{} Stardew Valley mods are tracked on Nexus", mod_ids.len());
+    Ok(mod_ids)
+}
+
+// Pass the file_id on to download_nexus_file to install it
+#[tauri::command]
+async fn get_nexus_primary_file(mod_id: u32) -> Result<NexusFileInfo, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let api_key = nexus_api_key(&settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    
+    let response: NexusFilesResponse = nexus_api_get(&format!("games/stardewvalley/mods/{}/files.json", mod_id), api_key, &rate_limit, &settings)
+        .await
+        .map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound(format!("Mod {} not found on Nexus", mod_id)),
+            e => e,
+        })?;
+    
+    // Not every author marks a primary file, the newest main file is the next best guess
+    let mut files = response.files;
+    let index = files
+        .iter()
+        .position(|file| file.is_primary)
+        .or_else(|| {
+            files
+                .iter()
+                .enumerate()
+                .filter(|(_, file)| file.category_name.as_deref() == Some("MAIN"))
+                .max_by_key(|(_, file)| file.uploaded_timestamp)
+                .map(|(index, _)| index)
+        })
+        .ok_or_else(|| AppError::NotFound(format!("Mod {} has no main file on Nexus", mod_id)))?;
+    let file = files.swap_remove(index);
+    
+    info!("Primary file of Nexus mod {} is {} ({:?})", mod_id, file.file_id, file.version);
+    Ok(file)
+}
+
+#[tauri::command]
+async fn download_nexus_file(app: tauri::AppHandle, mod_id: u32, file_id: u32, mods_path: String, key: Option<String>, expires: Option<String>) -> Result<NexusDownloadResult, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let api_key = nexus_api_key(&settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    let files_page_url = format!("https://www.nexusmods.com/stardewvalley/mods/{}?tab=files&file_id={}", mod_id, file_id);
+    
+    // Free accounts can only get a download link with the key from an nxm:// link,
+    // otherwise the file has to be downloaded through the website
+    let endpoint = format!("games/stardewvalley/mods/{}/files/{}/download_link.json", mod_id, file_id);
+    let mut query = Vec::new();
+    match (key.as_deref(), expires.as_deref()) {
+        (Some(key), Some(expires)) => query.extend([("key", key), ("expires", expires)]),
+        _ => {
+            let user: NexusUser = nexus_api_get("users/validate.json", api_key, &rate_limit, &settings).await?;
+            if !user.is_premium {
+                info!("Nexus account is not premium, sending the user to {}", files_page_url);
+                return Ok(NexusDownloadResult::ManualDownload { url: files_page_url });
+            }
+        }
+    }
+    
+    let links: Vec<NexusDownloadLink> = nexus_api_get_with_query(&endpoint, &query, api_key, &rate_limit, &settings).await?;
+    let link = links
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("Nexus returned no download link for file {} of mod {}", file_id, mod_id)))?;
+    
+    info!("Downloading Nexus file {} of mod {} from {}", file_id, mod_id, link.short_name);
+    let download_path = unique_temp_path("nexus").with_extension("zip");
+    let progress_name = format!("nexus-{}-{}", mod_id, file_id);
+    
+    let result = match download_mod_archive(&app, &progress_name, &link.uri, &download_path).await {
+        Ok(_) => install_zip(&download_path, Path::new(&mods_path), false),
+        Err(e) => Err(e),
+    };
+    let _ = fs::remove_file(&download_path);
+    
+    Ok(NexusDownloadResult::Installed { message: result? })
+}
+
+fn handle_nxm_url(app: &tauri::AppHandle, url: &str) {
+    info!("Received nxm link: {}", url);
+    
+    let link = match parse_nxm_url(url) {
+        Ok(link) => link,
+        Err(e) => {
+            warn!("Ignoring nxm link {}: {}", url, e);
+            let _ = app.emit("nxm-download", NxmDownloadEvent {
+                mod_id: 0,
+                file_id: 0,
+                success: false,
+                message: e.to_string(),
+                manual_url: None,
+            });
+            return;
+        }
+    };
+    
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mods_path = get_settings().ok().and_then(|settings| settings.mods_path);
+        let result = match mods_path {
+            Some(mods_path) => {
+                let mods_path = mods_path.to_string_lossy().to_string();
+                download_nexus_file(app.clone(), link.mod_id, link.file_id, mods_path, link.key, link.expires).await
+            }
+            None => Err(AppError::NotFound("Choose the Mods folder before installing mods from Nexus".to_string())),
+        };
+        
+        let (success, message, manual_url) = match result {
+            Ok(NexusDownloadResult::Installed { message }) => (true, message, None),
+            Ok(NexusDownloadResult::ManualDownload { url }) => (false, "Download the file from the Nexus website".to_string(), Some(url)),
+            Err(e) => {
+                error!("Error installing nxm link for mod {}: {}", link.mod_id, e);
+                (false, e.to_string(), None)
+            }
+        };
+        
+        let _ = app.emit("nxm-download", NxmDownloadEvent {
+            mod_id: link.mod_id,
+            file_id: link.file_id,
+            success,
+            message,
+            manual_url,
+        });
+    });
+}
+
+// nxm://stardewvalley/mods/<mod id>/files/<file id>?key=...&expires=...&user_id=...
+fn parse_nxm_url(url: &str) -> Result<NxmLink, AppError> {
+    let url = tauri::Url::parse(url).map_err(|e| AppError::Parse(format!("Invalid nxm link: {}", e)))?;
+    
+    if !url.scheme().eq_ignore_ascii_case("nxm") {
+        return Err(AppError::Invalid(format!("Not an nxm link: {}", url)));
+    }
+    
+    if !url.host_str().is_some_and(|game| game.eq_ignore_ascii_case("stardewvalley")) {
+        return Err(AppError::Invalid(format!("The nxm link is not for Stardew Valley: {}", url)));
+    }
+    
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    
+    let (mod_id, file_id) = match segments.as_slice() {
+        [mods, mod_id, files, file_id] if mods.eq_ignore_ascii_case("mods") && files.eq_ignore_ascii_case("files") => {
+            (mod_id.parse::<u32>().ok(), file_id.parse::<u32>().ok())
+        }
+        _ => (None, None),
+    };
+    
+    let (mod_id, file_id) = match (mod_id, file_id) {
+        (Some(mod_id), Some(file_id)) => (mod_id, file_id),
+        _ => return Err(AppError::Invalid(format!("Unsupported nxm link: {}", url))),
+    };
+    
+    let query_value = |name: &str| url.query_pairs().find(|(key, _)| key == name).map(|(_, value)| value.to_string());
+    
+    Ok(NxmLink {
+        mod_id,
+        file_id,
+        key: query_value("key"),
+        expires: query_value("expires"),
+    })
+}
+
+fn nexus_api_key(settings: &AppSettings) -> Result<&str, AppError> {
+    settings.nexus_api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| AppError::Auth("A Nexus API key is required, add one in the settings".to_string()))
+}
+
+#[tauri::command]
+fn get_settings() -> Result<AppSettings, AppError> {
+    let settings_path = get_settings_path()?;
+    
+    if !settings_path.exists() {
+        return Ok(AppSettings::default());
+    }
+    
+    match read_settings_file(&settings_path) {
+        Ok(settings) => Ok(settings),
+        Err(e) => {
+            error!("Error reading settings: {}", e);
+            
+            // A damaged file shouldn't cost the user their API key, the last good copy is next to it
+            match read_settings_file(&settings_backup_path(&settings_path)) {
+                Ok(settings) => {
+                    warn!("Using the settings backup instead");
+                    Ok(settings)
+                }
+                Err(_) => Ok(AppSettings::default()),
+            }
+        }
+    }
+}
+
+fn read_settings_file(path: &Path) -> Result<AppSettings, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| AppError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str::<AppSettings>(&content).map_err(|e| AppError::Parse(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+fn settings_backup_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_extension("json.bak")
+}
+
+#[tauri::command]
+fn save_settings(settings: AppSettings) -> Result<(), AppError> {
+    let settings_path = get_settings_path()?;
+    
+    if let Some(proxy_url) = proxy_url(&settings) {
+        reqwest::Proxy::all(proxy_url).map_err(|e| AppError::Invalid(format!("Invalid proxy URL {}: {}", proxy_url, e)))?;
+    }
+    
+    // Ensure the parent directory exists
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create settings directory: {}", e)))?;
+    }
+    
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| AppError::Parse(format!("Failed to serialize settings: {}", e)))?;
+    let _guard = SETTINGS_WRITE.lock().unwrap();
+    
+    // Only a file that still parses is worth keeping as the backup
+    if read_settings_file(&settings_path).is_ok() {
+        if let Err(e) = fs::copy(&settings_path, settings_backup_path(&settings_path)) {
+            warn!("Failed to back up settings: {}", e);
+        }
+    }
+    
+    write_atomic(&settings_path, json.as_bytes()).map_err(|e| AppError::Io(format!("Failed to write settings: {}", e)))?;
+    
+    info!("Settings saved to: {}", settings_path.display());
+    Ok(())
+}
+
+#[tauri::command]
+async fn check_app_update(current_version: String) -> Result<UpdateInfo, AppError> {
+    // The prerelease setting is meant for mods, the manager itself only offers stable builds
+    let settings = AppSettings { include_prereleases: false, ..get_settings().unwrap_or_default() };
+    let (release, latest_version) = fetch_github_release(APP_REPOSITORY, None, &settings).await?;
+    let update_available = version_compare(&current_version, &latest_version);
+    
+    // Installer for this platform, or the release page when none matches
+    let asset_suffixes: &[&str] = if cfg!(target_os = "windows") {
+        &["-setup.exe", ".msi"]
+    } else if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else {
+        &[".appimage", ".deb", ".rpm"]
+    };
+    let arch = if cfg!(target_arch = "aarch64") { "aarch64" } else { "x64" };
+    let download_url = asset_suffixes
+        .iter()
+        .find_map(|suffix| {
+            let candidates: Vec<&GitHubAsset> = release.assets.iter().filter(|asset| asset.name.to_lowercase().ends_with(suffix)).collect();
+            candidates
+                .iter()
+                .find(|asset| asset.name.contains(arch))
+                .or(candidates.first())
+                .map(|asset| asset.browser_download_url.clone())
+        })
+        .unwrap_or(release.html_url);
+    
+    info!("Latest manager release: {} (running {})", latest_version, current_version);
+    Ok(UpdateInfo {
+        current_version,
+        latest_version,
+        update_available,
+        download_url: Some(download_url),
+        update_status: UpdateStatus::from_update_available(update_available),
+        checked_via: format!("GitHub:{}", APP_REPOSITORY),
+        failed_checks: Vec::new(),
+        rate_limit_reset: None,
+    })
+}
+
+#[tauri::command]
+fn pin_mod(folder_name: String, version: String) -> Result<(), AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mut settings = get_settings()?;
+    settings.pinned_versions.insert(enabled_folder_name(&folder_name), version.trim().to_string());
+    save_settings(settings)?;
+    
+    info!("Pinned {} at version {}", folder_name, version.trim());
+    Ok(())
+}
+
+#[tauri::command]
+fn unpin_mod(folder_name: String) -> Result<(), AppError> {
+    let mut settings = get_settings()?;
+    if settings.pinned_versions.remove(&enabled_folder_name(&folder_name)).is_some() {
+        save_settings(settings)?;
+        info!("Unpinned {}", folder_name);
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+fn set_mod_note(unique_id: String, tags: Vec<String>, note: String) -> Result<(), AppError> {
+    let key = unique_id.trim().to_lowercase();
+    if key.is_empty() {
+        return Err(AppError::Invalid("Notes need a mod with a unique ID".to_string()));
+    }
+    
+    let mut unique_tags: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+        if !unique_tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+            unique_tags.push(tag.to_string());
+        }
+    }
+    let note = note.trim().to_string();
+    
+    // An empty note removes the entry instead of keeping a blank one around
+    let mut settings = get_settings()?;
+    if unique_tags.is_empty() && note.is_empty() {
+        settings.mod_notes.remove(&key);
+    } else {
+        settings.mod_notes.insert(key, ModNote { tags: unique_tags, note });
+    }
+    save_settings(settings)?;
+    
+    info!("Updated the note for {}", unique_id);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_mod_notes() -> Result<HashMap<String, ModNote>, AppError> {
+    Ok(get_settings()?.mod_notes)
+}
+
+#[tauri::command]
+fn open_url(url: String) -> Result<(), AppError> {
+    use std::process::Command;
+    
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg(&url)
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open URL: {}", e)))?;
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd")
+            .args(&["/C", "start", &url])
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open URL: {}", e)))?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open")
+            .arg(&url)
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open URL: {}", e)))?;
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+fn open_folder(path: String) -> Result<(), AppError> {
+    use std::process::Command;
+    
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| AppError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+fn open_mod_folder(mods_path: String, folder_name: String) -> Result<(), AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    open_folder(mod_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn open_mod_homepage(mods_path: String, folder_name: String) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    // Not part of SMAPI's manifest format, but some authors add it anyway
+    let homepage = find_manifest(&mod_path)
+        .and_then(|manifest_path| fs::read_to_string(manifest_path).ok())
+        .and_then(|content| parse_manifest_as::<serde_json::Value>(&content).ok())
+        .and_then(|manifest| {
+            let fields = manifest.as_object()?;
+            fields
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("homepage"))
+                .and_then(|(_, value)| value.as_str())
+                .map(|url| url.trim().to_string())
+        })
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"));
+    if let Some(url) = homepage {
+        info!("Opening homepage of {}: {}", folder_name, url);
+        open_url(url.clone())?;
+        return Ok(url);
+    }
+    
+    // Otherwise local docs, the readme wins over a docs folder
+    let entries: Vec<fs::DirEntry> = fs::read_dir(&mod_path)
+        .map_err(|e| AppError::Io(format!("Failed to read mod folder: {}", e)))?
+        .flatten()
+        .collect();
+    let find_entry = |name: &str, is_dir: bool| {
+        entries
+            .iter()
+            .find(|entry| entry.file_name().to_string_lossy().eq_ignore_ascii_case(name) && entry.path().is_dir() == is_dir)
+            .map(|entry| entry.path())
+    };
+    let docs = find_entry("README.md", false)
+        .or_else(|| find_entry("README.txt", false))
+        .or_else(|| find_entry("docs", true))
+        .ok_or_else(|| AppError::NotFound(format!("{} has no homepage or readme", folder_name)))?;
+    
+    let docs = docs.to_string_lossy().to_string();
+    info!("Opening docs of {}: {}", folder_name, docs);
+    open_folder(docs.clone())?;
+    Ok(docs)
+}
+
+#[tauri::command]
+fn open_smapi_log() -> Result<(), AppError> {
+    let log_path = get_smapi_log_path()?;
+    
+    if !log_path.exists() {
+        return Err(AppError::NotFound(format!("SMAPI log not found at {}. Launch the game with SMAPI at least once to create it", log_path.display())));
+    }
+    
+    open_folder(log_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn parse_smapi_errors(mods: Option<Vec<ModInfo>>) -> Result<Vec<SmapiModError>, AppError> {
+    let log_path = get_smapi_log_path()?;
+    let content = match fs::read_to_string(&log_path) {
+        Ok(content) => content,
+        Err(_) => {
+            info!("No SMAPI log at {}", log_path.display());
+            return Ok(Vec::new());
+        }
+    };
+    
+    // The log names mods, not unique IDs, so match them against the installed names
+    let ids_by_name: HashMap<String, String> = mods
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|mod_info| !mod_info.unique_id.is_empty())
+        .map(|mod_info| (mod_info.name.to_lowercase(), mod_info.unique_id))
+        .collect();
+    
+    // Lines look like "[12:34:56 ERROR SMAPI] message", the source is the mod name for mod errors
+    let line_re = regex::Regex::new(r"^\[\d{2}:\d{2}:\d{2} (\w+)\s+([^\]]+)\] ?(.*)$").unwrap();
+    let skipped_re = regex::Regex::new(r"^- (.+?)(?: \d[^\s]*)? because (.+)$").unwrap();
+    
+    let mut errors: Vec<SmapiModError> = Vec::new();
+    let mut in_skipped = false;
+    for line in content.lines() {
+        let Some(captures) = line_re.captures(line) else {
+            continue;
+        };
+        let level = &captures[1];
+        let source = captures[2].trim();
+        let message = captures[3].trim();
+        
+        if source == "SMAPI" {
+            if message == "Skipped mods" {
+                in_skipped = true;
+                continue;
+            }
+            // The section is a block of consecutive ERROR lines
+            if level != "ERROR" {
+                in_skipped = false;
+                continue;
+            }
+            if in_skipped {
+                if let Some(skipped) = skipped_re.captures(message) {
+                    errors.push(SmapiModError {
+                        mod_name: skipped[1].to_string(),
+                        reason: skipped[2].to_string(),
+                        unique_id: None,
+                    });
+                }
+            }
+        } else if level == "ERROR" && source != "game" && !message.is_empty() {
+            errors.push(SmapiModError {
+                mod_name: source.to_string(),
+                reason: message.to_string(),
+                unique_id: None,
+            });
+        }
+    }
+    
+    // A failing mod tends to log the same error every tick
+    let mut seen = HashSet::new();
+    errors.retain(|error| seen.insert((error.mod_name.clone(), error.reason.clone())));
+    for error in &mut errors {
+        error.unique_id = ids_by_name.get(&error.mod_name.to_lowercase()).cloned();
+    }
+    
+    info!("Found {} mod errors in the SMAPI log", errors.len());
+    Ok(errors)
+}
+
+#[tauri::command]
+fn detect_smapi(game_path: String) -> Result<SmapiInfo, AppError> {
+    let executable_path = match find_smapi_executable(Path::new(&game_path)) {
+        Some(path) => path,
+        None => {
+            info!("SMAPI not found in {}", game_path);
+            return Ok(SmapiInfo {
+                installed: false,
+                version: None,
+                executable_path: None,
+            });
+        }
+    };
+    
+    // The deps file next to the executable names the installed SMAPI build
+    let deps_path = executable_path.with_file_name("StardewModdingAPI.deps.json");
+    let version = fs::read_to_string(&deps_path)
+        .ok()
+        .and_then(|content| {
+            let deps_re = regex::Regex::new(r#""StardewModdingAPI/(\d[^"]*)""#).unwrap();
+            deps_re.captures(&content).map(|captures| captures[1].to_string())
+        })
+        .or_else(smapi_version_from_log);
+    
+    info!("SMAPI found at {} (version {:?})", executable_path.display(), version);
+    Ok(SmapiInfo {
+        installed: true,
+        version,
+        executable_path: Some(executable_path),
+    })
+}
+
+// The first log line looks like "[SMAPI] SMAPI 4.0.8 with Stardew Valley 1.6.8 ..."
+fn smapi_version_from_log() -> Option<String> {
+    let content = fs::read_to_string(get_smapi_log_path().ok()?).ok()?;
+    let log_re = regex::Regex::new(r"SMAPI (\d[^\s]*) with Stardew Valley").unwrap();
+    log_re.captures(&content).map(|captures| captures[1].to_string())
+}
+
+#[tauri::command]
+fn detect_game_version(game_path: String) -> Result<Option<String>, AppError> {
+    // The assembly is the source of truth, the SMAPI log is only as fresh as the last launch
+    let version = find_game_assembly(Path::new(&game_path))
+        .and_then(|assembly| game_version_from_assembly(&assembly))
+        .or_else(game_version_from_log);
+    
+    match &version {
+        Some(version) => info!("Detected Stardew Valley {}", version),
+        None => warn!("Could not determine the game version in {}", game_path),
+    }
+    Ok(version)
+}
+
+fn find_game_assembly(game_path: &Path) -> Option<PathBuf> {
+    // Since 1.5.5 the game code lives in a .NET dll on every platform, older builds only have the exe
+    let candidates: Vec<PathBuf> = if cfg!(target_os = "windows") {
+        vec![game_path.join("Stardew Valley.dll"), game_path.join("Stardew Valley.exe"), game_path.join("StardewValley.exe")]
+    } else if cfg!(target_os = "macos") {
+        let contents_macos = game_path.join("Contents/MacOS");
+        vec![contents_macos.join("Stardew Valley.dll"), game_path.join("Stardew Valley.dll"), contents_macos.join("StardewValley.exe")]
+    } else {
+        vec![game_path.join("Stardew Valley.dll"), game_path.join("StardewValley.exe")]
+    };
+    
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+// Reads the FileVersion string of the assembly's version resource, e.g. "1.6.8.24119"
+fn game_version_from_assembly(assembly_path: &Path) -> Option<String> {
+    let bytes = fs::read(assembly_path).ok()?;
+    let key: Vec<u8> = "FileVersion\0".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let key_end = bytes.windows(key.len()).position(|window| window == key.as_slice())? + key.len();
+    
+    // The value follows the key after padding to a 32-bit boundary
+    let value: Vec<u16> = bytes[key_end..]
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .skip_while(|&unit| unit == 0)
+        .take_while(|&unit| unit != 0)
+        .take(64)
+        .collect();
+    let file_version = String::from_utf16(&value).ok()?;
+    
+    // The fourth part is the build number, the game itself reports "1.6.8"
+    let parts: Vec<&str> = file_version.trim().split('.').take(3).collect();
+    if parts.is_empty() || !parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    Some(parts.join("."))
+}
+
+fn game_version_from_log() -> Option<String> {
+    let content = fs::read_to_string(get_smapi_log_path().ok()?).ok()?;
+    let log_re = regex::Regex::new(r"with Stardew Valley (\d[^\s]*)").unwrap();
+    log_re.captures(&content).map(|captures| captures[1].to_string())
+}
+
+#[tauri::command]
+fn check_smapi_compatibility(mods: Vec<ModInfo>, smapi_version: String) -> Result<HashMap<String, String>, AppError> {
+    let mut incompatible = HashMap::new();
+    
+    // SMAPI skips mods that need a newer API than the one installed
+    for mod_info in mods {
+        if let Some(required) = mod_info.minimum_api_version {
+            if version_compare(&smapi_version, &required) {
+                info!("{} requires SMAPI {} (installed: {})", mod_info.folder_name, required, smapi_version);
+                incompatible.insert(mod_info.folder_name, required);
+            }
+        }
+    }
+    
+    Ok(incompatible)
+}
+
+#[tauri::command]
+async fn check_compatibility_database(mods: Vec<ModInfo>) -> Result<HashMap<String, ModCompatibility>, AppError> {
+    let entries = load_compatibility_list().await?;
+    
+    // SMAPI compares unique IDs case-insensitively
+    let by_id: HashMap<String, &CompatibilityEntry> = entries
+        .iter()
+        .flat_map(|entry| entry.ids.iter().map(move |id| (id.to_lowercase(), entry)))
+        .collect();
+    
+    // Mods missing from the list are left out rather than reported as ok
+    let mut results = HashMap::new();
+    for mod_info in mods {
+        if mod_info.unique_id.is_empty() {
+            continue;
+        }
+        
+        if let Some(entry) = by_id.get(&mod_info.unique_id.to_lowercase()) {
+            if entry.status != CompatibilityStatus::Ok {
+                info!("{} is marked {:?} in the compatibility list", mod_info.folder_name, entry.status);
+            }
+            results.insert(mod_info.folder_name, ModCompatibility {
+                unique_id: mod_info.unique_id,
+                status: entry.status,
+                summary: entry.summary.clone(),
+                alternative_url: entry.alternative_url.clone(),
+            });
+        }
+    }
+    
+    Ok(results)
+}
+
+async fn load_compatibility_list() -> Result<Vec<CompatibilityEntry>, AppError> {
+    let cache_path = get_config_dir()?.join("compatibility_cache.json");
+    let cached = fs::read_to_string(&cache_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<CompatibilityCache>(&content).ok());
+    
+    if let Some(cache) = &cached {
+        if unix_timestamp().saturating_sub(cache.fetched_at) < COMPATIBILITY_CACHE_TTL_SECS {
+            debug!("Using cached compatibility list");
+            return Ok(cache.entries.clone());
+        }
+    }
+    
+    let settings = get_settings()?;
+    let entries = match fetch_compatibility_list(&settings).await {
+        Ok(entries) => entries,
+        // A stale list is still better than no warnings at all
+        Err(e) => match cached {
+            Some(cache) => {
+                warn!("Failed to refresh compatibility list, using the cached copy: {}", e);
+                return Ok(cache.entries);
+            }
+            None => return Err(e),
+        },
+    };
+    
+    let cache = CompatibilityCache { fetched_at: unix_timestamp(), entries };
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create cache directory: {}", e)))?;
+    }
+    let json = serde_json::to_string(&cache).map_err(|e| AppError::Parse(format!("Failed to serialize compatibility list: {}", e)))?;
+    if let Err(e) = fs::write(&cache_path, json) {
+        error!("Error writing compatibility cache: {}", e);
+    }
+    
+    Ok(cache.entries)
+}
+
+async fn fetch_compatibility_list(settings: &AppSettings) -> Result<Vec<CompatibilityEntry>, AppError> {
+    let client = http_client(settings)?;
+    let request = client
+        .get(COMPATIBILITY_LIST_URL)
+        .timeout(request_timeout(settings));
+    let response = send_with_retry(request, settings.max_retries)
+        .await
+        .map_err(|e| network_error(settings, "Failed to fetch compatibility list", e))?;
+    
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!("Compatibility list returned status: {}", response.status())));
+    }
+    
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::Parse(format!("Failed to parse compatibility list: {}", e)))
+}
+
+#[tauri::command]
+fn set_log_level(level: String) -> Result<(), AppError> {
+    let level = level.trim().to_lowercase();
+    let filter = log_filter(&level)?;
+    
+    if let Some(handle) = LOG_FILTER.get() {
+        handle.reload(filter).map_err(|e| AppError::Io(format!("Failed to change the log level: {}", e)))?;
+    }
+    
+    let mut settings = get_settings()?;
+    settings.log_level = level.clone();
+    save_settings(settings)?;
+    
+    info!("Log level set to {}", level);
+    Ok(())
+}
+
+fn log_filter(level: &str) -> Result<tracing_subscriber::EnvFilter, AppError> {
+    if !["error", "warn", "info", "debug", "trace"].contains(&level) {
+        return Err(AppError::Invalid(format!("Unknown log level: {}", level)));
+    }
+    
+    // Dependencies are too chatty below info, but quieter levels apply to them as well
+    let dependency_level = if matches!(level, "error" | "warn") { level } else { "info" };
+    Ok(tracing_subscriber::EnvFilter::new(format!("{},stardew_mod_manager_lib={}", dependency_level, level)))
+}
+
+#[tauri::command]
+fn open_log_file() -> Result<(), AppError> {
+    let config_dir = get_config_dir()?;
+    
+    // Log files rotate daily, the newest one holds the current session
+    let latest_log = fs::read_dir(&config_dir)
+        .map_err(|e| AppError::Io(format!("Failed to read log directory: {}", e)))?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(LOG_FILE_PREFIX) && name.ends_with(".log")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|metadata| metadata.modified()).ok())
+        .ok_or_else(|| AppError::NotFound(format!("No log file found in {}", config_dir.display())))?;
+    
+    open_folder(latest_log.path().to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn launch_game(game_path: String) -> Result<(), AppError> {
+    use std::process::Command;
+    
+    let smapi_path = find_smapi_executable(Path::new(&game_path)).ok_or_else(|| {
+        AppError::NotFound(format!("SMAPI was not found in {}. Install SMAPI from https://smapi.io to play with mods", game_path))
+    })?;
+    
+    let working_dir = smapi_path.parent().unwrap_or(Path::new(&game_path));
+    
+    // Spawn without waiting so the manager stays responsive while the game runs
+    Command::new(&smapi_path)
+        .current_dir(working_dir)
+        .spawn()
+        .map_err(|e| AppError::Io(format!("Failed to launch SMAPI: {}", e)))?;
+    
+    info!("Launched SMAPI: {}", smapi_path.display());
+    Ok(())
+}
+
+#[tauri::command]
+fn supported_update_providers() -> Result<Vec<UpdateProvider>, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    let has_nexus_key = nexus_api_key(&settings).is_ok();
+    
+    // Keep in sync with check_update_key
+    let providers = [("Nexus", true, true), ("GitHub", false, true), ("ModDrop", false, false), ("Chucklefish", false, false)];
+    Ok(providers
+        .into_iter()
+        .map(|(prefix, requires_api_key, automatic_check)| UpdateProvider {
+            prefix,
+            requires_api_key,
+            automatic_check,
+            available: automatic_check && !settings.offline && (!requires_api_key || has_nexus_key),
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn check_single_mod_update_frontend(mod_info: ModInfo) -> Result<UpdateInfo, AppError> {
+    debug!("Frontend verification request for mod: {} ({})", mod_info.name, mod_info.version);
+    debug!("Update keys: {:?}", mod_info.update_keys);
+    let settings = get_settings().unwrap_or_default();
+    let cache = Mutex::new(load_update_cache());
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    let result = check_single_mod_update(&mod_info, &settings, &cache, &rate_limit, &UpdateKeyLookups::default(), false).await;
+    debug!("Verification result: {:?}", result);
+    
+    if let Err(e) = save_update_cache(&cache.into_inner().unwrap()) {
+        error!("Error saving update cache: {}", e);
+    }
+    
+    result
+}
+
+#[tauri::command]
+fn update_manifest_version(mods_path: String, mod_folder_name: String, new_version: String) -> Result<(), AppError> {
+    validate_folder_name(&mod_folder_name)?;
+    
+    debug!("mods_path: {}", mods_path);
+    debug!("mod_folder_name: {}", mod_folder_name);
+    debug!("new_version: {}", new_version);
+    use regex::Regex;
+    
+    let mod_path = Path::new(&mods_path).join(&mod_folder_name);
+    let manifest_path = find_manifest(&mod_path)
+        .ok_or_else(|| AppError::NotFound("Manifest.json not found".to_string()))?;
+    
+    // Read the current manifest
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .map_err(|e| AppError::Io(format!("Failed to read manifest: {}", e)))?;
+    
+    // Use regex to replace the version
+    let version_re = Regex::new(r#""Version"\s*:\s*"([^"]+)""#).unwrap();
+    let new_manifest = version_re.replace(&manifest_content, &format!(r#""Version": "{}""#, new_version));
+    
+    // Write the updated manifest back
+    fs::write(&manifest_path, new_manifest.as_bytes())
+        .map_err(|e| AppError::Io(format!("Failed to write updated manifest: {}", e)))?;
+    
+    info!("Updated manifest version for {} to {}", mod_folder_name, new_version);
+    Ok(())
+}
+
+#[tauri::command]
+fn read_manifest(mods_path: String, folder_name: String) -> Result<serde_json::Value, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    let manifest_path = find_manifest(&mod_path)
+        .ok_or_else(|| AppError::NotFound(format!("{} has no manifest.json", folder_name)))?;
+    
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| AppError::Io(format!("Failed to read manifest: {}", e)))?;
+    
+    // Keep every key, including custom ones the structured Manifest ignores
+    let manifest: serde_json::Value = parse_manifest_as(&content)?;
+    if !manifest.is_object() {
+        return Err(AppError::Parse(format!("The manifest of {} is not a JSON object", folder_name)));
+    }
+    
+    Ok(manifest)
+}
+
+#[tauri::command]
+fn read_mod_config(mods_path: String, folder_name: String) -> Result<Option<serde_json::Value>, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    // SMAPI writes config.json next to the manifest, on the mod's first launch
+    let config_dir = find_manifest(&mod_path)
+        .and_then(|manifest_path| manifest_path.parent().map(Path::to_path_buf))
+        .unwrap_or(mod_path);
+    let config_path = config_dir.join("config.json");
+    if !config_path.is_file() {
+        return Ok(None);
+    }
+    
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| AppError::Io(format!("Failed to read config.json: {}", e)))?;
+    
+    // Same leniency as manifests, configs are often edited by hand
+    json5::from_str(content.trim_start_matches('\u{feff}'))
+        .map(Some)
+        .map_err(|e| AppError::Parse(format!("Invalid config.json in {}: {}", folder_name, e)))
+}
+
+#[tauri::command]
+fn verify_mod(mods_path: String, folder_name: String) -> Result<ModVerification, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    Ok(verify_mod_folder(&mod_path, folder_name))
+}
+
+#[tauri::command]
+fn verify_all_mods(mods_path: String) -> Result<Vec<ModVerification>, AppError> {
+    let mods_dir = Path::new(&mods_path);
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_path)));
+    }
+    
+    // Broken mods don't show up in scan_mods, so walk the folders directly
+    let mut folders = Vec::new();
+    collect_verifiable_folders(mods_dir, "", 0, &mut folders);
+    folders.sort();
+    
+    let reports: Vec<ModVerification> = folders
+        .into_iter()
+        .map(|folder_name| verify_mod_folder(&mods_dir.join(&folder_name), folder_name))
+        .collect();
+    
+    info!("Verified {} mods, {} with problems", reports.len(), reports.iter().filter(|report| !report.healthy).count());
+    Ok(reports)
+}
+
+fn collect_verifiable_folders(dir: &Path, prefix: &str, depth: usize, folders: &mut Vec<String>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            continue;
+        }
+        
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("__") || name.ends_with(BACKUP_SUFFIX) {
+            continue;
+        }
+        
+        let path = entry.path();
+        let folder_name = format!("{}{}", prefix, name);
+        
+        // A plain folder whose subfolders hold manifests is a group, not a broken mod
+        let is_group = depth < MAX_MOD_GROUP_DEPTH
+            && !name.starts_with('.')
+            && find_manifest(&path).is_none()
+            && fs::read_dir(&path)
+                .map(|children| children.flatten().any(|child| child.path().is_dir() && find_manifest(&child.path()).is_some()))
+                .unwrap_or(false);
+        
+        if is_group {
+            collect_verifiable_folders(&path, &format!("{}/", folder_name), depth + 1, folders);
+        } else {
+            folders.push(folder_name);
+        }
+    }
+}
+
+fn verify_mod_folder(mod_path: &Path, folder_name: String) -> ModVerification {
+    let mut problems = Vec::new();
+    
+    let is_empty = fs::read_dir(mod_path).map(|mut entries| entries.next().is_none()).unwrap_or(true);
+    if is_empty {
+        problems.push(VerifyProblem::EmptyFolder);
+    } else {
+        match find_manifest(mod_path) {
+            None => problems.push(VerifyProblem::MissingManifest),
+            Some(manifest_path) => {
+                let manifest = fs::read_to_string(&manifest_path)
+                    .map_err(|e| AppError::Io(format!("Failed to read manifest: {}", e)))
+                    .and_then(|content| parse_manifest(&content));
+                
+                match manifest {
+                    Err(e) => problems.push(VerifyProblem::InvalidManifest { message: e.to_string() }),
+                    Ok(manifest) => {
+                        let entry_dll = manifest.entry_dll.as_deref().map(str::trim).filter(|dll| !dll.is_empty());
+                        let is_content_pack = manifest.content_pack_for.is_some();
+                        
+                        match entry_dll {
+                            Some(dll) if !mod_path.join(dll).is_file() => {
+                                problems.push(VerifyProblem::MissingEntryDll { file: dll.to_string() });
+                            }
+                            None if !is_content_pack => problems.push(VerifyProblem::NoEntryPoint),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    ModVerification {
+        folder_name,
+        healthy: problems.is_empty(),
+        problems,
+    }
+}
+
+// With the marker mechanism, disabling doesn't stop SMAPI from loading the mod, see EnableMechanism
+#[tauri::command]
+fn set_mod_enabled(mods_path: String, folder_name: String, enabled: bool) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    toggle_mod_folder(Path::new(&mods_path), &folder_name, enabled)
+}
+
+#[tauri::command]
+fn rename_mod_folder(mods_path: String, old_name: String, new_name: String) -> Result<String, AppError> {
+    validate_folder_name(&old_name)?;
+    
+    // Only the mod's own folder is renamed, it stays in the same group
+    let new_name = new_name.trim();
+    // A leading dot would disable the mod, and also covers "." and ".."
+    if new_name.is_empty() || new_name.starts_with('.') || new_name.contains(['/', '\\', ':']) {
+        return Err(AppError::Invalid(format!("Invalid mod folder name: {}", new_name)));
+    }
+    
+    let (group, name) = match old_name.rsplit_once('/') {
+        Some((group, name)) => (Some(group), name),
+        None => (None, old_name.as_str()),
+    };
+    let qualify = |name: String| match group {
+        Some(group) => format!("{}/{}", group, name),
+        None => name,
+    };
+    
+    // Keep the mod disabled if it was
+    let disabled = name.starts_with('.');
+    let target_name = qualify(if disabled { format!(".{}", new_name) } else { new_name.to_string() });
+    
+    let mods_dir = Path::new(&mods_path);
+    let from_path = mods_dir.join(&old_name);
+    if !from_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", old_name)));
+    }
+    
+    if target_name == old_name {
+        return Ok(target_name);
+    }
+    
+    // A folder under either state of the new name would clash as soon as the mod is toggled,
+    // unless it's the same folder with a different case
+    let same_folder = enabled_folder_name(&target_name).eq_ignore_ascii_case(&enabled_folder_name(&old_name));
+    let clashes = [qualify(new_name.to_string()), qualify(format!(".{}", new_name))]
+        .iter()
+        .any(|candidate| mods_dir.join(candidate).exists());
+    if clashes && !same_folder {
+        return Err(AppError::Invalid(format!("Cannot rename {} to {}: a folder with that name already exists", old_name, new_name)));
+    }
+    
+    fs::rename(&from_path, mods_dir.join(&target_name))
+        .map_err(|e| AppError::Io(format!("Failed to rename mod folder: {}", e)))?;
+    
+    info!("Renamed mod folder: {} -> {}", old_name, target_name);
+    Ok(target_name)
+}
+
+#[tauri::command]
+fn move_mod(mods_path: String, folder_name: String, destination_subfolder: String) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    // An empty destination moves the mod back to the root of the Mods folder
+    let destination = destination_subfolder.trim().trim_matches('/');
+    if !destination.is_empty() {
+        validate_folder_name(destination)?;
+        
+        // Deeper folders wouldn't be picked up by scan_mods, and SMAPI skips folders starting with a dot
+        if destination.split('/').count() > MAX_MOD_GROUP_DEPTH || destination.starts_with('.') {
+            return Err(AppError::Invalid(format!("Mods can only be moved into a top-level group folder, not {}", destination)));
+        }
+    }
+    
+    let mods_dir = Path::new(&mods_path);
+    let from_path = mods_dir.join(&folder_name);
+    if !from_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    if find_manifest(&from_path).is_none() {
+        return Err(AppError::Invalid(format!("{} is not a mod folder", folder_name)));
+    }
+    
+    let destination_dir = mods_dir.join(destination);
+    if !destination.is_empty() && find_manifest(&destination_dir).is_some() {
+        return Err(AppError::Invalid(format!("{} is a mod, not a group folder", destination)));
+    }
+    
+    // The name keeps its dot prefix so a disabled mod stays disabled
+    let name = folder_name.rsplit('/').next().unwrap_or(&folder_name);
+    let target_name = if destination.is_empty() { name.to_string() } else { format!("{}/{}", destination, name) };
+    if target_name == folder_name {
+        return Ok(target_name);
+    }
+    
+    let base_name = name.strip_prefix('.').unwrap_or(name);
+    let clashes = [base_name.to_string(), format!(".{}", base_name)]
+        .iter()
+        .any(|candidate| destination_dir.join(candidate).exists());
+    if clashes {
+        return Err(AppError::Invalid(format!("A folder named {} already exists in {}", base_name, if destination.is_empty() { "the Mods folder" } else { destination })));
+    }
+    
+    fs::create_dir_all(&destination_dir)
+        .map_err(|e| AppError::Io(format!("Failed to create folder {}: {}", destination, e)))?;
+    fs::rename(&from_path, mods_dir.join(&target_name))
+        .map_err(|e| AppError::Io(format!("Failed to move mod folder: {}", e)))?;
+    
+    info!("Moved mod folder: {} -> {}", folder_name, target_name);
+    Ok(target_name)
+}
+
+#[tauri::command]
+fn organize_loose_mod(mods_path: String) -> Result<String, AppError> {
+    let mods_dir = Path::new(&mods_path);
+    let manifest_path = find_manifest(mods_dir)
+        .ok_or_else(|| AppError::NotFound("There is no loose manifest.json in the Mods folder".to_string()))?;
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| AppError::Io(format!("Failed to read manifest: {}", e)))?;
+    let manifest = parse_manifest(&content)?;
+    
+    let folder_name = [manifest.name.as_deref(), manifest.unique_id.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(sanitize_folder_name)
+        .find(|name| !name.is_empty())
+        .ok_or_else(|| AppError::Invalid("The loose manifest has neither a Name nor a UniqueID to name the folder after".to_string()))?;
+    
+    let target_dir = mods_dir.join(&folder_name);
+    if target_dir.exists() || mods_dir.join(format!(".{}", folder_name)).exists() {
+        return Err(AppError::Invalid(format!("A folder named {} already exists in the Mods directory", folder_name)));
+    }
+    
+    // Every loose file belongs to the mod, folders only when they aren't other mods or groups
+    let is_mod_or_group = |dir: &Path| {
+        find_manifest(dir).is_some()
+            || fs::read_dir(dir).is_ok_and(|entries| entries.flatten().any(|entry| entry.path().is_dir() && find_manifest(&entry.path()).is_some()))
+    };
+    let entries: Vec<PathBuf> = fs::read_dir(mods_dir)
+        .map_err(|e| AppError::Io(format!("Failed to read mods directory: {}", e)))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            path.is_file() || !(name.starts_with('.') || name.starts_with("__") || name.ends_with(BACKUP_SUFFIX) || is_mod_or_group(path))
+        })
+        .collect();
+    
+    fs::create_dir(&target_dir).map_err(|e| AppError::Io(format!("Failed to create mod folder: {}", e)))?;
+    for entry in &entries {
+        if let Some(name) = entry.file_name() {
+            fs::rename(entry, target_dir.join(name))
+                .map_err(|e| AppError::Io(format!("Failed to move {} into {}: {}", entry.display(), folder_name, e)))?;
+        }
+    }
+    
+    info!("Moved {} loose entries into {}", entries.len(), folder_name);
+    Ok(folder_name)
+}
+
+#[tauri::command]
+fn stage_mod(mods_path: String, folder_name: String) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mods_dir = Path::new(&mods_path);
+    let from_path = mods_dir.join(&folder_name);
+    if !from_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    // Staging already keeps SMAPI away, so the dot prefix isn't needed there
+    let staged_name = enabled_folder_name(&folder_name);
+    let to_path = staging_dir(mods_dir)?.join(&staged_name);
+    if to_path.exists() {
+        return Err(AppError::Invalid(format!("{} is already staged, remove it from {} first", staged_name, STAGING_FOLDER_NAME)));
+    }
+    
+    if let Some(parent) = to_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create staging folder: {}", e)))?;
+    }
+    move_dir(&from_path, &to_path)?;
+    
+    info!("Staged mod: {} -> {}", folder_name, to_path.display());
+    Ok(staged_name)
+}
+
+#[tauri::command]
+fn unstage_mod(mods_path: String, folder_name: String) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mods_dir = Path::new(&mods_path);
+    let staging_dir = staging_dir(mods_dir)?;
+    let from_path = staging_dir.join(&folder_name);
+    if !from_path.is_dir() {
+        return Err(AppError::NotFound(format!("Staged mod not found: {}", folder_name)));
+    }
+    
+    // Either state of the same name in Mods would clash
+    let enabled_name = enabled_folder_name(&folder_name);
+    let disabled_name = match enabled_name.rsplit_once('/') {
+        Some((group, name)) => format!("{}/.{}", group, name),
+        None => format!(".{}", enabled_name),
+    };
+    if mods_dir.join(&enabled_name).exists() || mods_dir.join(&disabled_name).exists() {
+        return Err(AppError::Invalid(format!("A mod folder named {} already exists in the Mods directory", enabled_name)));
+    }
+    
+    let to_path = mods_dir.join(&enabled_name);
+    if let Some(parent) = to_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create mod folder: {}", e)))?;
+    }
+    move_dir(&from_path, &to_path)?;
+    
+    // Don't leave empty group folders behind in staging
+    if let Some(group_dir) = from_path.parent().filter(|dir| *dir != staging_dir) {
+        let _ = fs::remove_dir(group_dir);
+    }
+    
+    info!("Unstaged mod: {} -> {}", folder_name, enabled_name);
+    Ok(enabled_name)
+}
+
+fn staging_dir(mods_dir: &Path) -> Result<PathBuf, AppError> {
+    mods_dir
+        .parent()
+        .map(|parent| parent.join(STAGING_FOLDER_NAME))
+        .ok_or_else(|| AppError::Invalid(format!("Invalid mods directory: {}", mods_dir.display())))
+}
+
+#[tauri::command]
+fn disable_all_mods(mods_path: String) -> Result<BulkToggleResult, AppError> {
+    set_all_mods_enabled(&mods_path, false)
+}
+
+#[tauri::command]
+fn enable_all_mods(mods_path: String) -> Result<BulkToggleResult, AppError> {
+    set_all_mods_enabled(&mods_path, true)
+}
+
+fn set_all_mods_enabled(mods_path: &str, enabled: bool) -> Result<BulkToggleResult, AppError> {
+    let mods_dir = Path::new(mods_path);
+    let mut result = BulkToggleResult::default();
+    
+    for mod_info in scan_mods(mods_path.to_string(), None, None, None)? {
+        if mod_info.enabled == enabled {
+            continue;
+        }
+        
+        // Turning off SMAPI's own mods would break the console and save backups
+        let base_name = enabled_folder_name(&mod_info.folder_name);
+        if SMAPI_BUNDLED_MODS.iter().any(|bundled| bundled.eq_ignore_ascii_case(&base_name)) {
+            info!("Skipping SMAPI bundled mod: {}", mod_info.folder_name);
+            continue;
+        }
+        
+        match toggle_mod_folder(mods_dir, &mod_info.folder_name, enabled) {
+            Ok(new_name) => result.record(new_name, enabled),
+            Err(e) => error!("Error toggling {}: {}", mod_info.folder_name, e),
+        }
+    }
+    
+    info!("{} {} mods", if enabled { "Enabled" } else { "Disabled" }, result.changed.len());
+    Ok(result)
+}
+
+
+#[tauri::command]
+fn set_mods_enabled_by_author(mods_path: String, author: String, enabled: bool) -> Result<BulkToggleResult, AppError> {
+    let author = author.trim().to_lowercase();
+    if author.is_empty() {
+        return Err(AppError::Invalid("An author name is required".to_string()));
+    }
+    
+    let mods_dir = Path::new(&mods_path);
+    let mut result = BulkToggleResult::default();
+    
+    for mod_info in scan_mods(mods_path.clone(), None, None, None)? {
+        // Co-authored mods list several names, e.g. "Alice, Bob & Carol"
+        let matches = mod_info.author
+            .split([',', '&'])
+            .any(|name| name.trim().to_lowercase() == author);
+        if !matches || mod_info.enabled == enabled {
+            continue;
+        }
+        
+        match toggle_mod_folder(mods_dir, &mod_info.folder_name, enabled) {
+            Ok(new_name) => result.record(new_name, enabled),
+            Err(e) => error!("Error toggling {}: {}", mod_info.folder_name, e),
+        }
+    }
+    
+    info!("{} {} mods by {}", if enabled { "Enabled" } else { "Disabled" }, result.changed.len(), author);
+    Ok(result)
+}
+
+#[tauri::command]
+fn backup_mods_folder(mods_path: String, backup_dir: String) -> Result<ModsBackupResult, AppError> {
+    use std::io::Write;
+    
+    let mods_dir = Path::new(&mods_path);
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_path)));
+    }
+    
+    let backup_dir = Path::new(&backup_dir);
+    fs::create_dir_all(backup_dir).map_err(|e| AppError::Io(format!("Failed to create backup directory: {}", e)))?;
+    
+    // The archive would end up zipping itself
+    if let (Ok(backup_dir), Ok(mods_dir)) = (backup_dir.canonicalize(), mods_dir.canonicalize()) {
+        if backup_dir.starts_with(&mods_dir) {
+            return Err(AppError::Invalid("The backup location can't be inside the Mods folder".to_string()));
+        }
+    }
+    
+    let backup_path = backup_dir.join(format!("mods-backup-{}.zip", unix_timestamp()));
+    info!("Backing up {} to {}", mods_dir.display(), backup_path.display());
+    
+    let file = fs::File::create(&backup_path)
+        .map_err(|e| AppError::Io(format!("Failed to create backup archive: {}", e)))?;
+    let mut writer = zip::ZipWriter::new(file);
+    
+    let result = add_dir_to_zip(&mut writer, mods_dir, mods_dir).and_then(|_| {
+        let mut file = writer.finish().map_err(|e| AppError::Io(format!("Failed to finish backup archive: {}", e)))?;
+        file.flush().map_err(|e| AppError::Io(format!("Failed to write backup archive: {}", e)))
+    });
+    
+    // Don't leave a truncated archive around that looks like a valid backup
+    if let Err(e) = result {
+        let _ = fs::remove_file(&backup_path);
+        return Err(e);
+    }
+    
+    let size_bytes = fs::metadata(&backup_path).map(|metadata| metadata.len()).unwrap_or(0);
+    info!("Mods backup created: {} ({} bytes)", backup_path.display(), size_bytes);
+    
+    Ok(ModsBackupResult {
+        path: backup_path,
+        size_bytes,
+    })
+}
+
+#[tauri::command]
+fn restore_mods_backup(zip_path: String, mods_path: String) -> Result<String, AppError> {
+    let zip_path = Path::new(&zip_path);
+    if !zip_path.is_file() {
+        return Err(AppError::NotFound(format!("Backup not found: {}", zip_path.display())));
+    }
+    
+    let mods_dir = Path::new(&mods_path);
+    let folder_name = mods_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Invalid(format!("Invalid mods directory: {}", mods_path)))?;
+    let parent = mods_dir
+        .parent()
+        .ok_or_else(|| AppError::Invalid(format!("Invalid mods directory: {}", mods_path)))?;
+    
+    // Extract next to the Mods folder first so a broken backup leaves the current mods untouched
+    let timestamp = unix_timestamp();
+    let staging_path = parent.join(format!("{}.restore-{}", folder_name, timestamp));
+    if let Err(e) = extract_archive(zip_path, &staging_path, false, &mut |_, _| {}) {
+        let _ = fs::remove_dir_all(&staging_path);
+        return Err(e);
+    }
+    
+    let previous_path = parent.join(format!("{}.before-restore-{}", folder_name, timestamp));
+    let had_mods = mods_dir.exists();
+    if had_mods {
+        fs::rename(mods_dir, &previous_path).map_err(|e| {
+            let _ = fs::remove_dir_all(&staging_path);
+            AppError::Io(format!("Failed to move the current mods folder aside: {}", e))
+        })?;
+    }
+    
+    if let Err(e) = fs::rename(&staging_path, mods_dir) {
+        let _ = fs::rename(&previous_path, mods_dir);
+        let _ = fs::remove_dir_all(&staging_path);
+        return Err(AppError::Io(format!("Failed to restore mods folder: {}", e)));
+    }
+    
+    info!("Restored mods backup {} into {}", zip_path.display(), mods_dir.display());
+    if !had_mods {
+        return Ok(format!("Restored mods from {}", zip_path.display()));
+    }
+    
+    // The replaced mods are never deleted for good, in case the backup turns out to be bad
+    match trash::delete(&previous_path) {
+        Ok(()) => {
+            info!("Moved the previous mods folder to trash");
+            Ok(format!("Restored mods from {}, the previous mods were moved to the trash", zip_path.display()))
+        }
+        Err(trash_error) => {
+            warn!("Failed to move {} to trash: {}", previous_path.display(), trash_error);
+            Ok(format!("Restored mods from {}, the previous mods were kept in {}", zip_path.display(), previous_path.display()))
+        }
+    }
+}
+
+fn add_dir_to_zip<W: std::io::Write + std::io::Seek>(writer: &mut zip::ZipWriter<W>, base: &Path, dir: &Path) -> Result<(), AppError> {
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let entries = fs::read_dir(dir).map_err(|e| AppError::Io(format!("Failed to read {}: {}", dir.display(), e)))?;
+    
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let relative_path = match path.strip_prefix(base) {
+            Ok(relative_path) => relative_path,
+            Err(_) => continue,
+        };
+        
+        // Zip entries always use forward slashes
+        let name = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        
+        // Linked folders could point anywhere, even back up the tree
+        let Ok(metadata) = path.symlink_metadata() else {
+            continue;
+        };
+        if metadata.file_type().is_symlink() {
+            debug!("Skipping symlink in backup: {}", path.display());
+            continue;
+        }
+        
+        if metadata.is_dir() {
+            writer.add_directory(format!("{}/", name), options)
+                .map_err(|e| AppError::Io(format!("Failed to add {} to backup: {}", name, e)))?;
+            add_dir_to_zip(writer, base, &path)?;
+        } else {
+            writer.start_file(name.as_str(), options)
+                .map_err(|e| AppError::Io(format!("Failed to add {} to backup: {}", name, e)))?;
+            let mut file = fs::File::open(&path)
+                .map_err(|e| AppError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+            std::io::copy(&mut file, writer)
+                .map_err(|e| AppError::Io(format!("Failed to write {} to backup: {}", name, e)))?;
+        }
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+fn snapshot_mod(mods_path: String, folder_name: String, snapshot_dir: String) -> Result<ModsBackupResult, AppError> {
+    use std::io::Write;
+    
+    validate_folder_name(&folder_name)?;
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    let snapshot_dir = Path::new(&snapshot_dir);
+    fs::create_dir_all(snapshot_dir).map_err(|e| AppError::Io(format!("Failed to create snapshot directory: {}", e)))?;
+    if let (Ok(snapshot_dir), Ok(mod_path)) = (snapshot_dir.canonicalize(), mod_path.canonicalize()) {
+        if snapshot_dir.starts_with(&mod_path) {
+            return Err(AppError::Invalid("The snapshot location can't be inside the mod folder".to_string()));
+        }
+    }
+    
+    let name = sanitize_folder_name(&enabled_folder_name(&folder_name).replace('/', "_"));
+    let snapshot_path = snapshot_dir.join(format!("{}-snapshot-{}.zip", name, unix_timestamp()));
+    info!("Snapshotting {} to {}", folder_name, snapshot_path.display());
+    
+    let file = fs::File::create(&snapshot_path)
+        .map_err(|e| AppError::Io(format!("Failed to create snapshot archive: {}", e)))?;
+    let mut writer = zip::ZipWriter::new(file);
+    
+    // Entries keep the mod folder as their root, like a mod downloaded from Nexus
+    let base = mod_path.parent().unwrap_or(&mod_path);
+    let result = add_dir_to_zip(&mut writer, base, &mod_path).and_then(|_| {
+        let mut file = writer.finish().map_err(|e| AppError::Io(format!("Failed to finish snapshot archive: {}", e)))?;
+        file.flush().map_err(|e| AppError::Io(format!("Failed to write snapshot archive: {}", e)))
+    });
+    
+    if let Err(e) = result {
+        let _ = fs::remove_file(&snapshot_path);
+        return Err(e);
+    }
+    
+    let size_bytes = fs::metadata(&snapshot_path).map(|metadata| metadata.len()).unwrap_or(0);
+    info!("Mod snapshot created: {} ({} bytes)", snapshot_path.display(), size_bytes);
+    
+    Ok(ModsBackupResult {
+        path: snapshot_path,
+        size_bytes,
+    })
+}
+
+#[tauri::command]
+fn restore_mod_snapshot(zip_path: String, mods_path: String) -> Result<String, AppError> {
+    let zip_path = Path::new(&zip_path);
+    if !zip_path.is_file() {
+        return Err(AppError::NotFound(format!("Snapshot not found: {}", zip_path.display())));
+    }
+    let mods_dir = Path::new(&mods_path);
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_path)));
+    }
+    
+    let staging_path = unique_temp_path("snapshot");
+    let result = restore_snapshot_from_staging(zip_path, &staging_path, mods_dir);
+    if staging_path.exists() {
+        let _ = fs::remove_dir_all(&staging_path);
+    }
+    
+    result
+}
+
+fn restore_snapshot_from_staging(zip_path: &Path, staging_path: &Path, mods_dir: &Path) -> Result<String, AppError> {
+    extract_archive(zip_path, staging_path, true, &mut |_, _| {})?;
+    let snapshot = parse_mod_folder(staging_path)
+        .filter(|mod_info| !mod_info.unique_id.is_empty())
+        .ok_or_else(|| AppError::Invalid("The snapshot doesn't contain a valid mod".to_string()))?;
+    
+    // Put it back where the mod lives now, which keeps its group and enabled state
+    let folder_name = scan_mods(mods_dir.to_string_lossy().to_string(), None, None, None)?
+        .into_iter()
+        .find(|mod_info| mod_info.unique_id.eq_ignore_ascii_case(&snapshot.unique_id))
+        .map(|mod_info| mod_info.folder_name)
+        .unwrap_or_else(|| sanitize_folder_name(&snapshot.name));
+    if folder_name.is_empty() {
+        return Err(AppError::Invalid("Could not determine a folder name for the mod".to_string()));
+    }
+    
+    let mod_path = mods_dir.join(&folder_name);
+    let backup_path = mods_dir.join(format!("{}{}", folder_name, BACKUP_SUFFIX));
+    let has_backup = mod_path.exists();
+    if has_backup {
+        if backup_path.exists() {
+            fs::remove_dir_all(&backup_path)
+                .map_err(|e| AppError::Io(format!("Failed to remove old backup: {}", e)))?;
+        }
+        fs::rename(&mod_path, &backup_path)
+            .map_err(|e| AppError::Io(format!("Failed to move the current mod aside: {}", e)))?;
+    }
+    
+    if let Err(e) = move_dir(staging_path, &mod_path) {
+        if has_backup {
+            restore_backup(&mod_path, &backup_path)
+                .map_err(|restore_error| AppError::Io(format!("Failed to restore snapshot ({}) and {}", e, restore_error)))?;
+        }
+        return Err(e);
+    }
+    
+    if has_backup {
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+    
+    info!("Restored snapshot {} into {}", zip_path.display(), folder_name);
+    Ok(folder_name)
+}
+
+#[tauri::command]
+fn list_backup_folders(mods_path: String) -> Result<Vec<BackupFolder>, AppError> {
+    let mods_dir = Path::new(&mods_path);
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_path)));
+    }
+    
+    let mut backups = Vec::new();
+    collect_backup_folders(mods_dir, "", 0, &mut backups);
+    backups.sort_by(|a, b| a.folder_name.cmp(&b.folder_name));
+    Ok(backups)
+}
+
+#[tauri::command]
+fn cleanup_backups(mods_path: String) -> Result<BackupCleanupResult, AppError> {
+    let mods_dir = Path::new(&mods_path);
+    let mut result = BackupCleanupResult::default();
+    
+    for backup in list_backup_folders(mods_path.clone())? {
+        match fs::remove_dir_all(mods_dir.join(&backup.folder_name)) {
+            Ok(()) => {
+                result.freed_bytes += backup.size_bytes;
+                result.removed.push(backup.folder_name);
+            }
+            Err(e) => result.errors.push(format!("Failed to remove {}: {}", backup.folder_name, e)),
+        }
+    }
+    
+    info!("Removed {} backup folders, freed {} bytes", result.removed.len(), result.freed_bytes);
+    Ok(result)
+}
+
+fn collect_backup_folders(dir: &Path, prefix: &str, depth: usize, backups: &mut Vec<BackupFolder>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            continue;
+        }
+        
+        let name = entry.file_name().to_string_lossy().to_string();
+        let folder_name = format!("{}{}", prefix, name);
+        
+        if name.ends_with(BACKUP_SUFFIX) {
+            backups.push(BackupFolder {
+                size_bytes: dir_size(&entry.path()),
+                folder_name,
+            });
+        } else if depth < MAX_MOD_GROUP_DEPTH && !name.starts_with('.') && find_manifest(&entry.path()).is_none() {
+            // Backups of grouped mods sit next to them inside the group folder
+            collect_backup_folders(&entry.path(), &format!("{}/", folder_name), depth + 1, backups);
+        }
+    }
+}
+
+#[tauri::command]
+fn delete_mod(mods_path: String, folder_name: String, check_dependents: Option<bool>) -> Result<String, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_path = Path::new(&mods_path).join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
+    }
+    
+    // Refuse to break other mods unless the UI already confirmed it
+    if check_dependents.unwrap_or(false) {
+        let unique_id = parse_mod_folder(&mod_path).map(|mod_info| mod_info.unique_id).unwrap_or_default();
+        if !unique_id.is_empty() {
+            let dependents: Vec<String> = mods_depending_on(unique_id, scan_mods(mods_path.clone(), None, None, None)?)?
+                .into_iter()
+                .filter(|dependent| *dependent != folder_name)
+                .collect();
+            if !dependents.is_empty() {
+                return Err(AppError::HasDependents {
+                    message: format!("{} is required by: {}", folder_name, dependents.join(", ")),
+                    dependents,
+                });
+            }
+        }
+    }
+    
+    // Prefer the OS trash so the user can recover from mistakes
+    match trash::delete(&mod_path) {
+        Ok(()) => {
+            info!("Moved mod to trash: {}", folder_name);
+            Ok(format!("Moved {} to the trash", folder_name))
+        }
+        Err(trash_error) => {
+            warn!("Failed to move {} to trash: {}", folder_name, trash_error);
+            fs::remove_dir_all(&mod_path).map_err(|e| {
+                AppError::Io(format!("Failed to move {} to the trash ({}) and failed to delete it permanently: {}", folder_name, trash_error, e))
+            })?;
+            
+            info!("Permanently deleted mod: {}", folder_name);
+            Ok(format!("Could not move {} to the trash ({}), it was deleted permanently", folder_name, trash_error))
+        }
+    }
+}
+
+#[tauri::command]
+fn save_profile(name: String, enabled_folders: Vec<String>) -> Result<(), AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Invalid("Profile name cannot be empty".to_string()));
+    }
+    
+    // Profiles store the enabled folder names, without the disabled dot prefix
+    let enabled_folders = enabled_folders
+        .iter()
+        .map(|folder| folder.strip_prefix('.').unwrap_or(folder).to_string())
+        .collect();
+    
+    let mut profiles_file = load_profiles()?;
+    profiles_file.profiles.retain(|profile| profile.name != name);
+    profiles_file.profiles.push(ModProfile { name: name.clone(), enabled_folders });
+    save_profiles(&profiles_file)?;
+    
+    info!("Saved profile: {}", name);
+    Ok(())
+}
+
+#[tauri::command]
+fn list_profiles() -> Result<Vec<ModProfile>, AppError> {
+    Ok(load_profiles()?.profiles)
+}
+
+#[tauri::command]
+fn apply_profile(name: String, mods_path: String) -> Result<ProfileApplyResult, AppError> {
+    let profile = load_profiles()?
+        .profiles
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| AppError::NotFound(format!("Profile not found: {}", name)))?;
+    
+    let wanted: HashSet<String> = profile.enabled_folders.into_iter().collect();
+    let mods_dir = Path::new(&mods_path);
+    let mut result = ProfileApplyResult::default();
+    let mut installed = HashSet::new();
+    
+    for mod_info in scan_mods(mods_path.clone(), None, None, None)? {
+        let base_name = enabled_folder_name(&mod_info.folder_name);
+        let should_enable = wanted.contains(&base_name);
+        
+        if !should_enable {
+            result.unlisted.push(base_name.clone());
+        }
+        
+        if should_enable != mod_info.enabled {
+            match toggle_mod_folder(mods_dir, &mod_info.folder_name, should_enable) {
+                Ok(_) if should_enable => result.enabled.push(base_name.clone()),
+                Ok(new_name) => {
+                    if is_loaded_folder(&new_name) {
+                        result.still_loaded.push(base_name.clone());
+                    }
+                    result.disabled.push(base_name.clone());
+                }
+                Err(e) => result.errors.push(e.to_string()),
+            }
+        }
+        
+        installed.insert(base_name);
+    }
+    
+    result.missing = wanted.into_iter().filter(|folder| !installed.contains(folder)).collect();
+    result.missing.sort();
+    
+    info!("Applied profile {}: {} enabled, {} disabled, {} missing", name, result.enabled.len(), result.disabled.len(), result.missing.len());
+    Ok(result)
+}
+
+#[tauri::command]
+fn install_mod_from_zip(zip_path: String, mods_path: String, force: Option<bool>) -> Result<String, AppError> {
+    let zip_path = Path::new(&zip_path);
+    if !zip_path.is_file() {
+        return Err(AppError::NotFound(format!("Archive not found: {}", zip_path.display())));
+    }
+    
+    install_zip(zip_path, Path::new(&mods_path), force.unwrap_or(false))
+}
+
+#[tauri::command]
+fn import_mods_from_folder(source_dir: String, mods_path: String, overwrite: Option<bool>) -> Result<ModImportResult, AppError> {
+    let source = Path::new(&source_dir);
+    let mods_dir = Path::new(&mods_path);
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_dir.display())));
+    }
+    if source.canonicalize().ok() == mods_dir.canonicalize().ok() {
+        return Err(AppError::Invalid("The source folder is the Mods folder itself".to_string()));
+    }
+    
+    let mut source_mods = Vec::new();
+    scan_mods_with(source, false, |mod_info| source_mods.push(mod_info))?;
+    
+    let installed: HashMap<String, String> = scan_mods(mods_path.clone(), None, None, None)?
+        .into_iter()
+        .filter(|mod_info| !mod_info.unique_id.is_empty())
+        .map(|mod_info| (mod_info.unique_id.to_lowercase(), mod_info.folder_name))
+        .collect();
+    
+    let mut result = ModImportResult::default();
+    for mod_info in source_mods {
+        // Folders without a usable manifest would only be skipped by SMAPI too
+        if mod_info.needs_folder || mod_info.unique_id.is_empty() || mod_info.invalid.is_some() {
+            debug!("Skipping {} from {}: not a valid mod", mod_info.folder_name, source_dir);
+            result.skipped.push(mod_info.folder_name);
+            continue;
+        }
+        
+        // The same mod already installed keeps its folder name and enabled state
+        let target_name = match installed.get(&mod_info.unique_id.to_lowercase()) {
+            Some(_) if !overwrite.unwrap_or(false) => {
+                info!("Skipping {}, {} is already installed", mod_info.folder_name, mod_info.unique_id);
+                result.skipped.push(mod_info.folder_name);
+                continue;
+            }
+            Some(existing) => existing.clone(),
+            None => {
+                // Groups from another manager are flattened, only the mod's own folder is kept
+                let name = enabled_folder_name(&mod_info.folder_name);
+                let name = name.rsplit('/').next().unwrap_or(&name).to_string();
+                if mods_dir.join(&name).exists() || mods_dir.join(format!(".{}", name)).exists() {
+                    warn!("Not importing {}, a different folder named {} already exists", mod_info.folder_name, name);
+                    result.conflicted.push(mod_info.folder_name);
+                    continue;
+                }
+                name
+            }
+        };
+        
+        match import_mod_folder(&source.join(&mod_info.folder_name), mods_dir, &target_name) {
+            Ok(()) => {
+                info!("Imported {} into {}", mod_info.folder_name, target_name);
+                result.imported.push(target_name);
+            }
+            Err(e) => {
+                error!("Error importing {}: {}", mod_info.folder_name, e);
+                result.errors.push(format!("{}: {}", mod_info.folder_name, e.message()));
+            }
+        }
+    }
+    
+    info!("Imported {} mods from {} ({} skipped, {} conflicted)", result.imported.len(), source_dir, result.skipped.len(), result.conflicted.len());
+    Ok(result)
+}
+
+// An overwritten mod is kept as a backup until the copy is complete, so a failed import leaves it as it was
+fn import_mod_folder(source_path: &Path, mods_dir: &Path, target_name: &str) -> Result<(), AppError> {
+    let target_path = mods_dir.join(target_name);
+    let backup_path = mods_dir.join(format!("{}{}", target_name, BACKUP_SUFFIX));
+    let has_backup = target_path.exists();
+    if has_backup {
+        if backup_path.exists() {
+            fs::remove_dir_all(&backup_path)
+                .map_err(|e| AppError::Io(format!("Failed to remove old backup: {}", e)))?;
+        }
+        
+        fs::rename(&target_path, &backup_path)
+            .map_err(|e| AppError::Io(format!("Failed to back up existing mod {}: {}", target_name, e)))?;
+    }
+    
+    if let Err(e) = copy_dir_recursive(source_path, &target_path) {
+        if !has_backup {
+            let _ = fs::remove_dir_all(&target_path);
+            return Err(e);
+        }
+        
+        restore_backup(&target_path, &backup_path)
+            .map_err(|restore_error| AppError::Io(format!("Failed to copy mod ({}) and {}", e.message(), restore_error)))?;
+        return Err(AppError::Io(format!("Failed to copy mod, the existing version was restored: {}", e.message())));
+    }
+    
+    if has_backup {
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+async fn update_mod(app: tauri::AppHandle, mod_folder_name: String, download_url: String, mods_path: String, expected_md5: Option<String>, target_version: Option<String>) -> Result<ModUpdateResult, AppError> {
+    validate_folder_name(&mod_folder_name)?;
+    
+    if let Some(result) = already_up_to_date(&mods_path, &mod_folder_name, target_version.as_deref()) {
+        return Ok(result);
+    }
+    
+    let result = update_single_mod(&app, &mod_folder_name, &download_url, &mods_path, expected_md5.as_deref()).await;
+    
+    // Lets the UI patch the mod in place instead of scanning everything again
+    let event = match &result {
+        Ok(_) => ModUpdatedEvent {
+            version: parse_mod_folder(&Path::new(&mods_path).join(&mod_folder_name)).map(|mod_info| mod_info.version),
+            folder_name: mod_folder_name,
+            success: true,
+            error: None,
+        },
+        Err(e) => ModUpdatedEvent {
+            folder_name: mod_folder_name,
+            success: false,
+            version: None,
+            error: Some(e.clone()),
+        },
+    };
+    let _ = app.emit("mod-updated", &event);
+    
+    result
+}
+
+// The UI may be showing stale state, e.g. after the user updated the mod by hand
+fn already_up_to_date(mods_path: &str, folder_name: &str, target_version: Option<&str>) -> Option<ModUpdateResult> {
+    let target_version = target_version.map(str::trim).filter(|version| !version.is_empty())?;
+    let installed = parse_mod_folder(&Path::new(mods_path).join(folder_name))?.version;
+    if version_compare(&installed, target_version) {
+        return None;
+    }
+    
+    info!("{} is already at {} (target {}), skipping download", folder_name, installed, target_version);
+    Some(ModUpdateResult {
+        folder_name: folder_name.to_string(),
+        success: true,
+        message: format!("{} is already up to date ({})", folder_name, installed),
+        changes: None,
+    })
+}
+
+#[tauri::command]
+async fn repair_mod(app: tauri::AppHandle, mods_path: String, folder_name: String) -> Result<ModUpdateResult, AppError> {
+    validate_folder_name(&folder_name)?;
+    
+    let mod_info = parse_mod_folder(&Path::new(&mods_path).join(&folder_name))
+        .filter(|mod_info| !mod_info.unique_id.is_empty())
+        .ok_or_else(|| AppError::NotFound(format!("{} has no readable manifest, its version can't be determined", folder_name)))?;
+    
+    // Resolve the download before touching anything, so a mod is never left half reinstalled
+    let settings = get_settings().unwrap_or_default();
+    let download_url = resolve_repair_download(&mod_info, &settings).await?;
+    
+    info!("Repairing {} ({}) from {}", folder_name, mod_info.version, download_url);
+    update_single_mod(&app, &folder_name, &download_url, &mods_path, None).await
+}
+
+// Direct archive URL for the installed version of a mod, trying each update key in turn
+async fn resolve_repair_download(mod_info: &ModInfo, settings: &AppSettings) -> Result<String, AppError> {
+    if mod_info.update_keys.is_empty() {
+        return Err(AppError::Invalid(format!("{} has no update keys to download it from", mod_info.name)));
+    }
+    
+    let mut reasons = Vec::new();
+    for update_key in &mod_info.update_keys {
+        let result = match parse_update_key(update_key) {
+            Some((provider, mod_id, _)) if provider == "nexus" => nexus_version_download(mod_id, &mod_info.version, settings).await,
+            Some((provider, repo, subkey)) if provider == "github" => github_version_download(repo, subkey, &mod_info.version, settings).await,
+            _ => Err(AppError::Invalid("downloads from this source aren't supported".to_string())),
+        };
+        
+        match result {
+            Ok(url) => return Ok(url),
+            Err(e) => {
+                debug!("Can't repair {} through {}: {}", mod_info.name, update_key, e);
+                reasons.push(format!("{}: {}", update_key.trim(), e.message()));
+            }
+        }
+    }
+    
+    Err(AppError::NotFound(format!("No download of {} {} is available ({})", mod_info.name, mod_info.version, reasons.join("; "))))
+}
+
+async fn nexus_version_download(mod_id: &str, version: &str, settings: &AppSettings) -> Result<String, AppError> {
+    let api_key = nexus_api_key(settings)?;
+    let rate_limit = Mutex::new(NexusRateLimit::default());
+    
+    // Only premium accounts get download links without going through the website
+    let user: NexusUser = nexus_api_get("users/validate.json", api_key, &rate_limit, settings).await?;
+    if !user.is_premium {
+        return Err(AppError::Auth("Nexus only allows direct downloads for premium accounts".to_string()));
+    }
+    
+    let response: NexusFilesResponse = nexus_api_get(&format!("games/stardewvalley/mods/{}/files.json", mod_id), api_key, &rate_limit, settings).await?;
+    let version = normalize_version(version);
+    let file = response
+        .files
+        .iter()
+        .filter(|file| file.version.as_deref().is_some_and(|file_version| normalize_version(file_version) == version))
+        .max_by_key(|file| (file.is_primary, file.category_name.as_deref() == Some("MAIN"), file.uploaded_timestamp))
+        .ok_or_else(|| AppError::NotFound(format!("no file for version {} on Nexus", version)))?;
+    
+    let links: Vec<NexusDownloadLink> = nexus_api_get(&format!("games/stardewvalley/mods/{}/files/{}/download_link.json", mod_id, file.file_id), api_key, &rate_limit, settings).await?;
+    links
+        .into_iter()
+        .next()
+        .map(|link| link.uri)
+        .ok_or_else(|| AppError::NotFound(format!("Nexus returned no download link for file {}", file.file_id)))
+}
+
+async fn github_version_download(repo: &str, subkey: Option<&str>, version: &str, settings: &AppSettings) -> Result<String, AppError> {
+    let client = http_client(settings)?;
+    let request = client
+        .get(format!("https://api.github.com/repos/{}/releases", repo))
+        .timeout(request_timeout(settings));
+    let response = send_with_retry(request, settings.max_retries)
+        .await
+        .map_err(|e| network_error(settings, "Failed to fetch GitHub releases", e))?;
+    
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!("GitHub API returned status: {}", response.status())));
+    }
+    
+    let releases: Vec<GitHubRelease> = response
+        .json()
+        .await
+        .map_err(|e| AppError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+    
+    let version = normalize_version(version);
+    let release = releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .find(|release| extract_tag_version(&release.tag_name, subkey).is_some_and(|tag_version| normalize_version(&tag_version) == version))
+        .ok_or_else(|| AppError::NotFound(format!("no GitHub release for version {}", version)))?;
+    
+    // The source code archives GitHub adds aren't installable mods, only uploaded assets are
+    release
+        .assets
+        .iter()
+        .find(|asset| {
+            let name = asset.name.to_lowercase();
+            name.ends_with(".zip") || name.ends_with(".7z")
+        })
+        .map(|asset| asset.browser_download_url.clone())
+        .ok_or_else(|| AppError::NotFound(format!("GitHub release {} has no mod archive attached", release.tag_name)))
+}
+
+#[tauri::command]
+async fn update_mods(app: tauri::AppHandle, updates: Vec<(String, String)>, mods_path: String, max_concurrent: Option<usize>) -> Result<Vec<ModUpdateResult>, AppError> {
+    let targets = updates
+        .into_iter()
+        .map(|(folder_name, download_url)| UpdateTarget { folder_name, download_url, expected_md5: None, target_version: None })
+        .collect();
+    Ok(run_mod_updates(&app, targets, &mods_path, max_concurrent).await)
+}
+
+#[tauri::command]
+async fn update_all_mods(app: tauri::AppHandle, mods_path: String, updates: Vec<UpdateTarget>, max_concurrent: Option<usize>) -> Result<BulkUpdateSummary, AppError> {
+    info!("Updating {} mods", updates.len());
+    let (succeeded, failed): (Vec<ModUpdateResult>, Vec<ModUpdateResult>) = run_mod_updates(&app, updates, &mods_path, max_concurrent)
+        .await
+        .into_iter()
+        .partition(|result| result.success);
+    
+    info!("Bulk update finished: {} succeeded, {} failed", succeeded.len(), failed.len());
+    Ok(BulkUpdateSummary { succeeded, failed })
+}
+
+async fn run_mod_updates(app: &tauri::AppHandle, targets: Vec<UpdateTarget>, mods_path: &str, max_concurrent: Option<usize>) -> Vec<ModUpdateResult> {
+    // Limit simultaneous downloads so a big batch doesn't saturate the connection
+    let semaphore = tokio::sync::Semaphore::new(max_concurrent.unwrap_or(DEFAULT_MAX_CONCURRENT_DOWNLOADS).max(1));
+    
+    let tasks = targets.into_iter().map(|target| {
+        let semaphore = &semaphore;
+        async move {
+            let _permit = semaphore.acquire().await;
+            let UpdateTarget { folder_name, download_url, expected_md5, target_version } = target;
+            
+            // Loose mods in the Mods root have an empty folder name and are rejected here too
+            if let Err(e) = validate_folder_name(&folder_name) {
+                warn!("Skipping update of {:?}: {}", folder_name, e);
+                let result = ModUpdateResult { folder_name, success: false, message: e.to_string(), changes: None };
+                let _ = app.emit("mod-update-complete", &result);
+                return result;
+            }
+            
+            if let Some(result) = already_up_to_date(mods_path, &folder_name, target_version.as_deref()) {
+                let _ = app.emit("mod-update-complete", &result);
+                return result;
+            }
+            
+            // Each mod keeps its own backup, a failure only rolls back that mod
+            let result = match update_single_mod(app, &folder_name, &download_url, mods_path, expected_md5.as_deref()).await {
+                Ok(result) => result,
+                Err(e) => {
+                    error!("Error updating {}: {}", folder_name, e);
+                    ModUpdateResult { folder_name, success: false, message: e.to_string(), changes: None }
+                }
+            };
+            
+            let _ = app.emit("mod-update-complete", &result);
+            result
+        }
+    });
+    
+    futures::future::join_all(tasks).await
+}
+
+#[tauri::command]
+async fn preview_update(app: tauri::AppHandle, mod_folder_name: String, download_url: String, mods_path: String) -> Result<UpdatePreview, AppError> {
+    info!("Previewing update for mod: {} from {}", mod_folder_name, download_url);
+    
+    let download_path = unique_temp_path("preview").with_extension("zip");
+    let result = preview_downloaded_update(&app, &mod_folder_name, &download_url, &mods_path, &download_path).await;
+    
+    // Nothing is installed in a dry run, the download is always thrown away
+    let _ = fs::remove_file(&download_path);
+    
+    result
+}
+
+async fn preview_downloaded_update(app: &tauri::AppHandle, mod_folder_name: &str, download_url: &str, mods_path: &str, download_path: &Path) -> Result<UpdatePreview, AppError> {
+    download_mod_archive(app, mod_folder_name, download_url, download_path).await?;
+    
+    let mod_path = Path::new(mods_path).join(mod_folder_name);
+    let new_files = list_archive_files(download_path)?;
+    
+    let mut existing_files = HashSet::new();
+    if mod_path.is_dir() {
+        collect_relative_files(&mod_path, &mod_path, &mut existing_files)?;
+    }
+    
+    let mut preview = UpdatePreview::default();
+    for file in &new_files {
+        if existing_files.contains(file) {
+            preview.overwritten.push(file.to_string_lossy().to_string());
+        }
+        preview.files.push(file.to_string_lossy().to_string());
+    }
+    
+    // The update replaces the whole folder, so files missing from the archive go away
+    let new_files: HashSet<PathBuf> = new_files.into_iter().collect();
+    preview.removed = existing_files
+        .iter()
+        .filter(|file| !new_files.contains(*file))
+        .map(|file| file.to_string_lossy().to_string())
+        .collect();
+    preview.removed.sort();
+    
+    Ok(preview)
+}
+
+fn collect_relative_files(base: &Path, dir: &Path, files: &mut HashSet<PathBuf>) -> Result<(), AppError> {
+    let entries = fs::read_dir(dir).map_err(|e| AppError::Io(format!("Failed to read {}: {}", dir.display(), e)))?;
+    
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_relative_files(base, &path, files)?;
+        } else if let Ok(relative_path) = path.strip_prefix(base) {
+            files.insert(relative_path.to_path_buf());
+        }
+    }
+    
+    Ok(())
+}
+
+async fn update_single_mod(app: &tauri::AppHandle, mod_folder_name: &str, download_url: &str, mods_path: &str, expected_md5: Option<&str>) -> Result<ModUpdateResult, AppError> {
+    info!("Updating mod: {} from {}", mod_folder_name, download_url);
+    
+    // Unique per download, so two updates of the same mod never share an archive
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let download_path = std::env::temp_dir().join(format!("{}{}-{}-{}.zip", TEMP_DOWNLOAD_PREFIX, mod_folder_name.replace('/', "_"), std::process::id(), nanos));
+    
+    let actual_md5 = download_mod_archive(app, mod_folder_name, download_url, &download_path).await?;
+    
+    // Verify the download before touching the installed mod
+    if let Some(expected) = expected_md5.map(str::trim).filter(|md5| !md5.is_empty()) {
+        if !actual_md5.eq_ignore_ascii_case(expected) {
+            let _ = fs::remove_file(&download_path);
+            return Err(AppError::Network(format!("Checksum mismatch, update aborted (expected {}, got {})", expected, actual_md5)));
+        }
+        info!("Checksum verified for {}: {}", mod_folder_name, actual_md5);
+    }
+    
+    // Big content packs take a while to extract, keep that off the async runtime
+    let extraction = {
+        let app = app.clone();
+        let mods_path = mods_path.to_string();
+        let folder_name = mod_folder_name.to_string();
+        let download_path = download_path.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = install_update_archive(&download_path, Path::new(&mods_path), &folder_name, &mut |files_done, files_total| {
+                let _ = app.emit("extraction-progress", ExtractionProgress {
+                    folder_name: folder_name.clone(),
+                    files_done,
+                    files_total,
+                });
+            });
+            let _ = app.emit("extraction-complete", ExtractionComplete {
+                folder_name: folder_name.clone(),
+                success: result.is_ok(),
+            });
+            result
+        })
+    };
+    let result = extraction
+        .await
+        .map_err(|e| AppError::Io(format!("Mod extraction failed: {}", e)))
+        .and_then(|result| result);
+    
+    // Clean up temp file
+    let _ = fs::remove_file(&download_path);
+    
+    let changes = result?;
+    Ok(ModUpdateResult {
+        folder_name: mod_folder_name.to_string(),
+        success: true,
+        message: format!("Successfully updated mod: {}", mod_folder_name),
+        changes: Some(changes),
+    })
+}
+
+async fn download_mod_archive(app: &tauri::AppHandle, folder_name: &str, download_url: &str, download_path: &Path) -> Result<String, AppError> {
+    use std::io::Write;
+    
+    // Big archives can take a while, so only the connection is bounded by the timeout
+    let settings = get_settings().unwrap_or_default();
+    let client = http_client(&settings)?;
+    let mut response = send_with_retry(client.get(download_url), settings.max_retries)
+        .await
+        .map_err(|e| network_error(&settings, "Failed to download mod", e))?;
+    
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!("Download failed with status: {}", response.status())));
+    }
+    
+    // Content-Length is optional, the frontend shows indeterminate progress without it
+    let total = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut hasher = md5::Context::new();
+    
+    // Stream the body to the temp file so progress can be reported per chunk
+    let mut file = fs::File::create(download_path)
+        .map_err(|e| AppError::Io(format!("Failed to create temp file: {}", e)))?;
+    
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to read download content: {}", e)))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| AppError::Io(format!("Failed to write temp file: {}", e)))?;
+        hasher.consume(&chunk);
+        
+        downloaded += chunk.len() as u64;
+        let _ = app.emit("mod-download-progress", DownloadProgress {
+            folder_name: folder_name.to_string(),
+            downloaded,
+            total,
+        });
+    }
+    
+    Ok(format!("{:x}", hasher.compute()))
+}
+
+fn install_update_archive(download_path: &Path, mods_dir: &Path, mod_folder_name: &str, on_progress: &mut dyn FnMut(usize, usize)) -> Result<UpdateChanges, AppError> {
+    let mod_path = mods_dir.join(mod_folder_name);
+    
+    // Create backup of existing mod
+    let backup_path = mods_dir.join(format!("{}{}", mod_folder_name, BACKUP_SUFFIX));
+    let has_backup = mod_path.exists();
+    if has_backup {
+        // Remove old backup if it exists
+        if backup_path.exists() {
+            fs::remove_dir_all(&backup_path)
+                .map_err(|e| AppError::Io(format!("Failed to remove old backup: {}", e)))?;
+        }
+        
+        // Move current mod to backup
+        fs::rename(&mod_path, &backup_path)
+            .map_err(|e| AppError::Io(format!("Failed to create backup: {}", e)))?;
+    }
+    
+    // Extract new mod
+    if let Err(e) = extract_archive(download_path, &mod_path, true, on_progress) {
+        if !has_backup {
+            let _ = fs::remove_dir_all(&mod_path);
+            return Err(AppError::Io(format!("Failed to extract mod: {}", e)));
+        }
+        
+        restore_backup(&mod_path, &backup_path)
+            .map_err(|restore_error| AppError::Io(format!("Failed to extract update ({}) and {}", e, restore_error)))?;
+        return Err(AppError::Io(format!("Failed to extract update, the previous version was restored: {}", e)));
+    }
+    
+    // Compare against the previous version while it's still around
+    let changes = if has_backup {
+        diff_mod_folders(&backup_path, &mod_path).unwrap_or_else(|e| {
+            warn!("Could not compare {} with its previous version: {}", mod_folder_name, e);
+            UpdateChanges::default()
+        })
+    } else {
+        UpdateChanges::default()
+    };
+    if !changes.removed.is_empty() {
+        info!("Update of {} removed: {}", mod_folder_name, changes.removed.join(", "));
+    }
+    
+    // Remove backup only once extraction was successful
+    if has_backup {
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+    
+    Ok(changes)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UpdateInfo {
-    pub current_version: String,
-    pub latest_version: String,
-    pub update_available: bool,
-    pub download_url: Option<String>,
+// Extraction doesn't keep modification times, so only a different size counts as modified
+fn diff_mod_folders(old_path: &Path, new_path: &Path) -> Result<UpdateChanges, AppError> {
+    let mut old_files = HashSet::new();
+    let mut new_files = HashSet::new();
+    collect_relative_files(old_path, old_path, &mut old_files)?;
+    collect_relative_files(new_path, new_path, &mut new_files)?;
+    
+    let file_size = |path: PathBuf| fs::metadata(path).map(|metadata| metadata.len()).ok();
+    let display = |path: &PathBuf| path.to_string_lossy().replace('\\', "/");
+    
+    let mut changes = UpdateChanges {
+        added: new_files.difference(&old_files).map(display).collect(),
+        removed: old_files.difference(&new_files).map(display).collect(),
+        modified: old_files
+            .intersection(&new_files)
+            .filter(|path| file_size(old_path.join(path)) != file_size(new_path.join(path)))
+            .map(display)
+            .collect(),
+    };
+    changes.added.sort();
+    changes.removed.sort();
+    changes.modified.sort();
+    
+    Ok(changes)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct NexusModInfo {
-    pub version: String,
-    pub mod_id: u32,
-    pub name: String,
+fn validate_folder_name(folder_name: &str) -> Result<(), AppError> {
+    // Folder names come from the frontend, make sure they can't escape the Mods directory.
+    // Grouped mods use "Group/Mod", so each component is checked on its own.
+    let is_invalid_component = |component: &str| {
+        component.trim().is_empty()
+            || component == "."
+            || component == ".."
+            || component.contains('\\')
+            || component.contains(':')
+    };
+    
+    if folder_name.split('/').any(is_invalid_component) {
+        return Err(AppError::Invalid(format!("Invalid mod folder name: {}", folder_name)));
+    }
+    
+    Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct NexusFileInfo {
-    pub version: String,
-    pub file_id: u32,
-    pub is_primary: bool,
+// SMAPI skips folders whose name starts with a dot
+fn is_loaded_folder(folder_name: &str) -> bool {
+    !folder_name.rsplit('/').next().is_some_and(|name| name.starts_with('.'))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct GitHubRelease {
-    pub tag_name: String,
-    pub html_url: String,
+// Folder name of a mod once enabled, the dot prefix sits on the last component
+fn enabled_folder_name(folder_name: &str) -> String {
+    match folder_name.rsplit_once('/') {
+        Some((parent, name)) => format!("{}/{}", parent, name.strip_prefix('.').unwrap_or(name)),
+        None => folder_name.strip_prefix('.').unwrap_or(folder_name).to_string(),
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AppSettings {
-    pub nexus_api_key: Option<String>,
+fn install_zip(zip_path: &Path, mods_dir: &Path, force: bool) -> Result<String, AppError> {
+    if !mods_dir.is_dir() {
+        return Err(AppError::NotFound(format!("Mods directory does not exist: {}", mods_dir.display())));
+    }
+    
+    // Extract somewhere temporary first so the manifest can decide the folder name
+    let staging_path = unique_temp_path("install");
+    let result = install_from_staging(zip_path, &staging_path, mods_dir, force);
+    
+    if staging_path.exists() {
+        let _ = fs::remove_dir_all(&staging_path);
+    }
+    
+    result
 }
 
-#[tauri::command]
-fn detect_stardew_valley() -> Result<StardewInfo, String> {
-    let possible_paths = get_stardew_paths();
+fn install_from_staging(zip_path: &Path, staging_path: &Path, mods_dir: &Path, force: bool) -> Result<String, AppError> {
+    let skipped = extract_archive(zip_path, staging_path, true, &mut |_, _| {})?;
     
-    if possible_paths.is_empty() {
-        return Err("No potential Stardew Valley installation paths found for this operating system".to_string());
+    // Either a single mod at the root, or a bundle of mods in subfolders
+    let root_mod = parse_mod_folder(staging_path).filter(|_| find_manifest(staging_path).is_some());
+    let staged_mods: Vec<ModInfo> = match &root_mod {
+        Some(_) => Vec::new(),
+        None => fs::read_dir(staging_path)
+            .map_err(|e| AppError::Io(format!("Failed to read extracted archive: {}", e)))?
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_dir()))
+            .filter_map(|entry| parse_mod_folder(&entry.path()))
+            .collect(),
+    };
+    
+    let archive_name = zip_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let (folder_name, staged_ids) = match &root_mod {
+        // Without a UniqueID the manifest couldn't be parsed, so the name isn't reliable either
+        Some(mod_info) if mod_info.unique_id.is_empty() => (sanitize_folder_name(&archive_name), Vec::new()),
+        Some(mod_info) => (sanitize_folder_name(&mod_info.name), vec![mod_info.unique_id.clone()]),
+        None if !staged_mods.is_empty() => (
+            sanitize_folder_name(&archive_name),
+            staged_mods.iter().map(|mod_info| mod_info.unique_id.clone()).collect(),
+        ),
+        None => return Err(AppError::Invalid("The archive doesn't contain a manifest.json, it doesn't look like a SMAPI mod".to_string())),
+    };
+    
+    if folder_name.is_empty() {
+        return Err(AppError::Invalid("Could not determine a folder name for the mod".to_string()));
     }
     
+    // Look for the same mod already installed under any folder name
+    let staged_ids: HashSet<String> = staged_ids
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_lowercase())
+        .collect();
+    let existing: Vec<ModInfo> = scan_mods(mods_dir.to_string_lossy().to_string(), None, None, None)?
+        .into_iter()
+        .filter(|mod_info| staged_ids.contains(&mod_info.unique_id.to_lowercase()))
+        .collect();
     
-    for path in possible_paths {
-        if is_stardew_directory(&path) {
-            // Check different possible Mods folder locations
-            let mut mods_path = None;
-            
-            // Standard location
-            let standard_mods = path.join("Mods");
-            if standard_mods.exists() {
-                mods_path = Some(standard_mods);
-            } else {
-                // macOS Steam version - check Contents/MacOS/Mods
-                let contents_macos_mods = path.join("Contents").join("MacOS").join("Mods");
-                if contents_macos_mods.exists() {
-                    mods_path = Some(contents_macos_mods);
-                } else {
-                    // Try Contents/Resources/Mods
-                    let contents_resources_mods = path.join("Contents").join("Resources").join("Mods");
-                    if contents_resources_mods.exists() {
-                        mods_path = Some(contents_resources_mods);
-                    }
-                }
-            }
-            
-            return Ok(StardewInfo {
-                game_path: Some(path),
-                mods_path,
-                found: true,
-            });
-        }
+    if !existing.is_empty() && !force {
+        let folders: Vec<&str> = existing.iter().map(|mod_info| mod_info.folder_name.as_str()).collect();
+        return Err(AppError::Invalid(format!("This mod is already installed in: {}. Use force to overwrite it", folders.join(", "))));
     }
     
-    Ok(StardewInfo {
-        game_path: None,
-        mods_path: None,
-        found: false,
-    })
+    // A mod the user had disabled stays disabled after being replaced
+    let folder_name = if !existing.is_empty() && existing.iter().all(|mod_info| !is_loaded_folder(&mod_info.folder_name)) {
+        format!(".{}", folder_name)
+    } else {
+        folder_name
+    };
+    
+    let target_path = mods_dir.join(&folder_name);
+    let target_is_existing = existing.iter().any(|mod_info| mod_info.folder_name == folder_name);
+    if target_path.exists() && !target_is_existing {
+        return Err(AppError::Invalid(format!("A different folder named {} already exists in the Mods directory", folder_name)));
+    }
+    
+    let existing_folders: Vec<String> = existing.into_iter().map(|mod_info| mod_info.folder_name).collect();
+    replace_mod_folders(staging_path, mods_dir, &existing_folders, &folder_name)?;
+    
+    if !skipped.is_empty() {
+        warn!("{} was installed without these unsafe archive entries: {}", folder_name, skipped.join(", "));
+    }
+    info!("Installed mod from {} into {}", zip_path.display(), folder_name);
+    Ok(folder_name)
 }
 
-#[tauri::command]
-fn scan_mods(mods_path: String) -> Result<Vec<ModInfo>, String> {
-    let path = Path::new(&mods_path);
-    let mut mods = Vec::new();
+// Existing copies are kept as backups until the new folder is in place, and restored if the move fails
+fn replace_mod_folders(staging_path: &Path, mods_dir: &Path, existing_folders: &[String], folder_name: &str) -> Result<(), AppError> {
+    let mut backups: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let restore_all = |backups: &[(PathBuf, PathBuf)]| {
+        for (mod_path, backup_path) in backups {
+            if let Err(e) = restore_backup(mod_path, backup_path) {
+                error!("Could not restore {}: {}", mod_path.display(), e);
+            }
+        }
+    };
     
-    if !path.exists() {
-        return Err(format!("Mods directory does not exist: {}", mods_path));
+    for existing_folder in existing_folders {
+        info!("Replacing existing mod folder: {}", existing_folder);
+        let mod_path = mods_dir.join(existing_folder);
+        let backup_path = mods_dir.join(format!("{}{}", existing_folder, BACKUP_SUFFIX));
+        if backup_path.exists() {
+            if let Err(e) = fs::remove_dir_all(&backup_path) {
+                restore_all(&backups);
+                return Err(AppError::Io(format!("Failed to remove old backup: {}", e)));
+            }
+        }
+        if let Err(e) = fs::rename(&mod_path, &backup_path) {
+            restore_all(&backups);
+            return Err(AppError::Io(format!("Failed to back up existing mod {}: {}", existing_folder, e)));
+        }
+        backups.push((mod_path, backup_path));
     }
     
-    if !path.is_dir() {
-        return Err(format!("Path is not a directory: {}", mods_path));
+    let target_path = mods_dir.join(folder_name);
+    if let Err(e) = move_dir(staging_path, &target_path) {
+        // A backup restored to the target path clears the partial copy itself
+        if !backups.iter().any(|(mod_path, _)| *mod_path == target_path) {
+            let _ = fs::remove_dir_all(&target_path);
+        }
+        restore_all(&backups);
+        return Err(AppError::Io(format!("Failed to install mod, the existing version was restored: {}", e.message())));
     }
     
-    match fs::read_dir(path) {
-        Ok(entries) => {
-            for entry in entries {
-                match entry {
-                    Ok(entry) => {
-                        if entry.file_type().map_or(false, |ft| ft.is_dir()) {
-                            if let Some(mod_info) = parse_mod_folder(&entry.path()) {
-                                mods.push(mod_info);
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        eprintln!("Error reading directory entry: {}", e);
-                    }
-                }
+    // Prefer the OS trash for the old copies, like delete_mod does
+    for (_, backup_path) in backups {
+        if let Err(trash_error) = trash::delete(&backup_path) {
+            warn!("Failed to move {} to trash: {}", backup_path.display(), trash_error);
+            let _ = fs::remove_dir_all(&backup_path);
+        }
+    }
+    
+    Ok(())
+}
+
+fn find_manifest(mod_path: &Path) -> Option<PathBuf> {
+    // Mods packed on Windows or macOS sometimes ship "Manifest.json", which a case-sensitive filesystem won't match
+    if let Ok(entries) = fs::read_dir(mod_path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() && entry.file_name().to_string_lossy().eq_ignore_ascii_case("manifest.json") {
+                return Some(path);
             }
-        },
-        Err(e) => {
-            return Err(format!("Failed to read mods directory: {}", e));
         }
     }
     
-    // Sort mods alphabetically by name, ignoring [CP] prefix
-    mods.sort_by(|a, b| {
-        let clean_name_a = a.name.strip_prefix("[CP] ").unwrap_or(&a.name).to_lowercase();
-        let clean_name_b = b.name.strip_prefix("[CP] ").unwrap_or(&b.name).to_lowercase();
-        clean_name_a.cmp(&clean_name_b)
-    });
+    let manifest_path = mod_path.join("manifest.json");
+    manifest_path.exists().then_some(manifest_path)
+}
+
+fn sanitize_folder_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') && !c.is_control())
+        .collect();
     
-    Ok(mods)
+    // Leading dots would disable the mod, trailing dots and spaces break Windows paths
+    sanitized.trim().trim_start_matches('.').trim_end_matches(['.', ' ']).to_string()
 }
 
 #[tauri::command]
-async fn check_mod_updates(mods: Vec<ModInfo>) -> Result<HashMap<String, UpdateInfo>, String> {
-    let mut updates = HashMap::new();
+fn cleanup_temp_downloads() -> Result<u64, AppError> {
+    let temp_dir = std::env::temp_dir();
+    let entries = fs::read_dir(&temp_dir)
+        .map_err(|e| AppError::Io(format!("Failed to read temp directory: {}", e)))?;
     
-    for mod_info in mods {
-        if !mod_info.update_keys.is_empty() {
-            match check_single_mod_update(&mod_info).await {
-                Ok(update_info) => {
-                    updates.insert(mod_info.folder_name, update_info);
-                }
-                Err(e) => {
-                    eprintln!("Error checking updates for {}: {}", mod_info.name, e);
-                    // Continue with other mods
-                }
+    // Archives left behind by updates and Nexus downloads interrupted by a crash
+    let mut removed = 0;
+    let mut bytes_reclaimed = 0;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let is_ours = file_name.starts_with(TEMP_DOWNLOAD_PREFIX) || file_name.starts_with("stardew-mod-manager-");
+        if !is_ours || !file_name.ends_with(".zip") {
+            continue;
+        }
+        
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        
+        let is_stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age.as_secs() >= TEMP_DOWNLOAD_MIN_AGE_SECS);
+        if !is_stale {
+            debug!("Keeping {}, it may still be downloading", file_name);
+            continue;
+        }
+        
+        match fs::remove_file(entry.path()) {
+            Ok(_) => {
+                removed += 1;
+                bytes_reclaimed += metadata.len();
             }
+            Err(e) => warn!("Failed to remove {}: {}", entry.path().display(), e),
         }
     }
     
-    Ok(updates)
+    info!("Removed {} leftover downloads, reclaimed {} bytes", removed, bytes_reclaimed);
+    Ok(bytes_reclaimed)
 }
 
-#[tauri::command]
-fn get_settings() -> Result<AppSettings, String> {
-    let settings_path = get_settings_path()?;
+fn unique_temp_path(label: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!("stardew-mod-manager-{}-{}-{}", label, std::process::id(), nanos))
+}
+
+fn move_dir(from: &Path, to: &Path) -> Result<(), AppError> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
     
-    if settings_path.exists() {
-        match fs::read_to_string(&settings_path) {
-            Ok(content) => {
-                match serde_json::from_str::<AppSettings>(&content) {
-                    Ok(settings) => Ok(settings),
-                    Err(e) => {
-                        eprintln!("Error parsing settings: {}", e);
-                        Ok(AppSettings { nexus_api_key: None })
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Error reading settings file: {}", e);
-                Ok(AppSettings { nexus_api_key: None })
-            }
+    // Renaming fails across filesystems (e.g. temp dir on another drive), copy instead
+    copy_dir_recursive(from, to)?;
+    fs::remove_dir_all(from).map_err(|e| AppError::Io(format!("Failed to remove {}: {}", from.display(), e)))
+}
+
+fn copy_dir_recursive(from: &Path, to: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(to).map_err(|e| AppError::Io(format!("Failed to create directory {}: {}", to.display(), e)))?;
+    
+    let entries = fs::read_dir(from).map_err(|e| AppError::Io(format!("Failed to read directory {}: {}", from.display(), e)))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| AppError::Io(format!("Failed to read directory entry: {}", e)))?;
+        let target = to.join(entry.file_name());
+        
+        if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| AppError::Io(format!("Failed to copy {}: {}", entry.path().display(), e)))?;
         }
-    } else {
-        Ok(AppSettings { nexus_api_key: None })
     }
+    
+    Ok(())
 }
 
-#[tauri::command]
-fn save_settings(settings: AppSettings) -> Result<(), String> {
-    let settings_path = get_settings_path()?;
-    
-    // Ensure the parent directory exists
-    if let Some(parent) = settings_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("Failed to create settings directory: {}", e))?;
+fn restore_backup(mod_path: &Path, backup_path: &Path) -> Result<(), AppError> {
+    // Drop whatever a failed extraction left behind before putting the backup back
+    if mod_path.exists() {
+        fs::remove_dir_all(mod_path)
+            .map_err(|e| AppError::Io(format!("failed to remove the partially extracted mod: {}", e)))?;
     }
     
-    let json = serde_json::to_string_pretty(&settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    fs::write(&settings_path, json).map_err(|e| format!("Failed to write settings: {}", e))?;
+    fs::rename(backup_path, mod_path)
+        .map_err(|e| AppError::Io(format!("failed to restore the backup from {}: {}", backup_path.display(), e)))?;
     
-    println!("Settings saved to: {}", settings_path.display());
+    info!("Restored backup for {}", mod_path.display());
     Ok(())
 }
 
-#[tauri::command]
-fn open_url(url: String) -> Result<(), String> {
-    use std::process::Command;
+fn toggle_mod_folder(mods_dir: &Path, folder_name: &str, enabled: bool) -> Result<String, AppError> {
+    let settings = get_settings().unwrap_or_default();
+    match settings.enable_mechanism {
+        EnableMechanism::Rename => {
+            let folder_name = toggle_mod_folder_name(mods_dir, folder_name, enabled)?;
+            if !enabled {
+                return Ok(folder_name);
+            }
+            
+            // Clear a marker left from before switching mechanisms, it would keep the mod disabled
+            let mod_path = mods_dir.join(&folder_name);
+            let has_marker = mod_path.join(DISABLED_MARKER).exists();
+            let is_listed = || {
+                parse_mod_folder(&mod_path).is_some_and(|mod_info| settings.disabled_mod_ids.contains(&mod_info.unique_id.to_lowercase()))
+            };
+            if has_marker || (!settings.disabled_mod_ids.is_empty() && is_listed()) {
+                return toggle_mod_marker(mods_dir, &folder_name, true);
+            }
+            Ok(folder_name)
+        }
+        EnableMechanism::Marker => toggle_mod_marker(mods_dir, folder_name, enabled),
+    }
+}
+
+fn toggle_mod_marker(mods_dir: &Path, folder_name: &str, enabled: bool) -> Result<String, AppError> {
+    // A mod disabled by renaming before the switch has to get its name back to be enabled
+    let folder_name = if enabled && folder_name.rsplit('/').next().is_some_and(|name| name.starts_with('.')) {
+        toggle_mod_folder_name(mods_dir, folder_name, true)?
+    } else {
+        folder_name.to_string()
+    };
     
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
+    let mod_path = mods_dir.join(&folder_name);
+    if !mod_path.is_dir() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
     }
     
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd")
-            .args(&["/C", "start", &url])
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
+    let marker_path = mod_path.join(DISABLED_MARKER);
+    if enabled {
+        if marker_path.exists() {
+            fs::remove_file(&marker_path).map_err(|e| AppError::Io(format!("Failed to remove disabled marker: {}", e)))?;
+        }
+    } else {
+        fs::write(&marker_path, "Disabled by Stardew Mod Manager\n")
+            .map_err(|e| AppError::Io(format!("Failed to write disabled marker: {}", e)))?;
     }
     
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
+    // The unique ID keeps the state when the folder is renamed or replaced by an update
+    if let Some(unique_id) = parse_mod_folder(&mod_path).map(|mod_info| mod_info.unique_id.to_lowercase()).filter(|id| !id.is_empty()) {
+        let mut settings = get_settings()?;
+        let listed = settings.disabled_mod_ids.contains(&unique_id);
+        if enabled && listed {
+            settings.disabled_mod_ids.retain(|id| *id != unique_id);
+            save_settings(settings)?;
+        } else if !enabled && !listed {
+            settings.disabled_mod_ids.push(unique_id);
+            save_settings(settings)?;
+        }
     }
     
-    Ok(())
+    info!("{} mod via marker: {}", if enabled { "Enabled" } else { "Disabled" }, folder_name);
+    Ok(folder_name)
 }
 
-#[tauri::command]
-fn open_folder(path: String) -> Result<(), String> {
-    use std::process::Command;
+fn toggle_mod_folder_name(mods_dir: &Path, folder_name: &str, enabled: bool) -> Result<String, AppError> {
+    // Grouped mods live in a subfolder, only their own folder gets renamed
+    let (group, name) = match folder_name.rsplit_once('/') {
+        Some((group, name)) => (Some(group), name),
+        None => (None, folder_name),
+    };
     
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    // SMAPI ignores folders starting with a dot, so the base name is the folder without it
+    let base_name = name.strip_prefix('.').unwrap_or(name);
+    if base_name.is_empty() {
+        return Err(AppError::Invalid("Invalid mod folder name".to_string()));
     }
     
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("explorer")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    let qualify = |name: String| match group {
+        Some(group) => format!("{}/{}", group, name),
+        None => name,
+    };
+    let enabled_name = qualify(base_name.to_string());
+    let disabled_name = qualify(format!(".{}", base_name));
+    
+    let (from_name, to_name) = if enabled {
+        (disabled_name, enabled_name)
+    } else {
+        (enabled_name, disabled_name)
+    };
+    
+    let from_path = mods_dir.join(&from_name);
+    let to_path = mods_dir.join(&to_name);
+    
+    // Already in the requested state
+    if to_path.exists() && !from_path.exists() {
+        return Ok(to_name);
     }
     
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    if !from_path.exists() {
+        return Err(AppError::NotFound(format!("Mod folder not found: {}", folder_name)));
     }
     
-    Ok(())
+    // Never overwrite another folder that already uses the target name
+    if to_path.exists() {
+        return Err(AppError::Invalid(format!("Cannot rename {} to {}: a folder with that name already exists", from_name, to_name)));
+    }
+    
+    fs::rename(&from_path, &to_path)
+        .map_err(|e| AppError::Io(format!("Failed to rename mod folder: {}", e)))?;
+    
+    info!("{} mod: {} -> {}", if enabled { "Enabled" } else { "Disabled" }, from_name, to_name);
+    Ok(to_name)
 }
 
-#[tauri::command]
-async fn check_single_mod_update_frontend(mod_info: ModInfo) -> Result<UpdateInfo, String> {
-    println!("Frontend verification request for mod: {} ({})", mod_info.name, mod_info.version);
-    println!("Update keys: {:?}", mod_info.update_keys);
-    let result = check_single_mod_update(&mod_info).await;
-    println!("Verification result: {:?}", result);
-    result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    SevenZip,
+    Rar,
 }
 
-#[tauri::command]
-fn update_manifest_version(mods_path: String, mod_folder_name: String, new_version: String) -> Result<(), String> {
-    println!("🔧 update_manifest_version called!");
-    println!("mods_path: {}", mods_path);
-    println!("mod_folder_name: {}", mod_folder_name);
-    println!("new_version: {}", new_version);
-    use regex::Regex;
+// Nexus file names don't always match their content, so the format comes from the magic bytes
+fn detect_archive_format(archive_path: &Path) -> Result<ArchiveFormat, AppError> {
+    use std::io::Read;
     
-    let mod_path = Path::new(&mods_path).join(&mod_folder_name);
-    let manifest_path = mod_path.join("manifest.json");
+    let mut header = [0u8; 6];
+    let read = fs::File::open(archive_path)
+        .and_then(|mut file| file.read(&mut header))
+        .map_err(|e| AppError::Io(format!("Failed to open archive: {}", e)))?;
+    let header = &header[..read];
     
-    if !manifest_path.exists() {
-        return Err("Manifest.json not found".to_string());
+    if header.starts_with(b"PK") {
+        Ok(ArchiveFormat::Zip)
+    } else if header.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        Ok(ArchiveFormat::SevenZip)
+    } else if header.starts_with(b"Rar!") {
+        Ok(ArchiveFormat::Rar)
+    } else {
+        Err(AppError::Invalid(format!("{} is not a zip, 7z or rar archive", archive_path.display())))
+    }
+}
+
+// Returns the names of entries that were skipped as unsafe, so callers can tell the user
+// on_progress gets (entries done, total entries) after each archive entry
+fn extract_archive(archive_path: &Path, extract_to: &Path, flatten: bool, on_progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<String>, AppError> {
+    match detect_archive_format(archive_path)? {
+        ArchiveFormat::Zip => extract_zip(archive_path, extract_to, flatten, on_progress),
+        ArchiveFormat::SevenZip => extract_7z(archive_path, extract_to, flatten, on_progress),
+        #[cfg(feature = "rar")]
+        ArchiveFormat::Rar => extract_rar(archive_path, extract_to, flatten, on_progress),
+        #[cfg(not(feature = "rar"))]
+        ArchiveFormat::Rar => Err(rar_unsupported_error()),
+    }
+}
+
+// Files extract_archive would write, relative to the mod folder
+fn list_archive_files(archive_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    match detect_archive_format(archive_path)? {
+        ArchiveFormat::Zip => list_zip_files(archive_path),
+        ArchiveFormat::SevenZip => list_7z_files(archive_path),
+        #[cfg(feature = "rar")]
+        ArchiveFormat::Rar => list_rar_files(archive_path),
+        #[cfg(not(feature = "rar"))]
+        ArchiveFormat::Rar => Err(rar_unsupported_error()),
+    }
+}
+
+// RAR decoding needs the native unrar library, only built with the "rar" feature
+#[cfg(not(feature = "rar"))]
+fn rar_unsupported_error() -> AppError {
+    AppError::Invalid("This build can't extract RAR archives. Extract it yourself and copy the mod folder into your Mods directory".to_string())
+}
+
+#[cfg(feature = "rar")]
+fn extract_rar(archive_path: &Path, extract_to: &Path, flatten: bool, on_progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<String>, AppError> {
+    let entries = rar_entries(archive_path)?;
+    let root = if flatten {
+        let entry_paths: Vec<PathBuf> = entries.iter().filter_map(|entry| sevenz_entry_path(&entry.filename.to_string_lossy())).collect();
+        find_mod_root(&entry_paths)
+    } else {
+        None
+    };
+    if let Some(root) = &root {
+        info!("Flattening archive root folder: {}", root.display());
     }
     
-    // Read the current manifest
-    let manifest_content = fs::read_to_string(&manifest_path)
-        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    fs::create_dir_all(extract_to)
+        .map_err(|e| AppError::Io(format!("Failed to create extraction directory: {}", e)))?;
     
-    // Use regex to replace the version
-    let version_re = Regex::new(r#""Version"\s*:\s*"([^"]+)""#).unwrap();
-    let new_manifest = version_re.replace(&manifest_content, &format!(r#""Version": "{}""#, new_version));
+    let rar_error = |e: unrar::error::UnrarError| AppError::Parse(format!("Failed to extract rar archive: {}", e));
+    let files_total = entries.len();
+    let mut files_done = 0;
+    let mut skipped = Vec::new();
+    let mut archive = unrar::Archive::new(archive_path).open_for_processing().map_err(rar_error)?;
+    while let Some(header) = archive.read_header().map_err(rar_error)? {
+        files_done += 1;
+        on_progress(files_done, files_total);
+        
+        let (name, is_directory, is_symlink) = {
+            let entry = header.entry();
+            (entry.filename.to_string_lossy().to_string(), entry.is_directory(), is_rar_symlink(entry))
+        };
+        
+        let relative_path = match sevenz_entry_path(&name) {
+            Some(entry_path) => zip_entry_relative_path(&entry_path, root.as_deref()),
+            None => {
+                warn!("Skipping unsafe path in archive: {}", name);
+                skipped.push(name.clone());
+                None
+            }
+        };
+        if is_symlink {
+            warn!("Skipping symlink in archive: {}", name);
+            skipped.push(name.clone());
+        }
+        
+        let outpath = match relative_path {
+            Some(relative_path) if !is_symlink => extract_to.join(relative_path),
+            _ => {
+                archive = header.skip().map_err(rar_error)?;
+                continue;
+            }
+        };
+        
+        if is_directory {
+            fs::create_dir_all(&outpath).map_err(|e| AppError::Io(format!("Failed to extract {}: {}", outpath.display(), e)))?;
+            archive = header.skip().map_err(rar_error)?;
+            continue;
+        }
+        
+        // Written here rather than by unrar, so only the checked path is ever touched
+        let (data, next) = header.read().map_err(rar_error)?;
+        outpath
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&outpath, data))
+            .map_err(|e| AppError::Io(format!("Failed to extract {}: {}", outpath.display(), e)))?;
+        archive = next;
+    }
     
-    // Write the updated manifest back
-    fs::write(&manifest_path, new_manifest.as_bytes())
-        .map_err(|e| format!("Failed to write updated manifest: {}", e))?;
+    if !skipped.is_empty() {
+        warn!("Skipped {} unsafe entries while extracting {}", skipped.len(), archive_path.display());
+    }
     
-    println!("Updated manifest version for {} to {}", mod_folder_name, new_version);
-    Ok(())
+    Ok(skipped)
 }
 
-#[tauri::command]
-async fn update_mod(mod_folder_name: String, download_url: String, mods_path: String) -> Result<String, String> {
-    use std::io::Write;
-    
-    println!("Updating mod: {} from {}", mod_folder_name, download_url);
+#[cfg(feature = "rar")]
+fn list_rar_files(archive_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let entry_paths: Vec<PathBuf> = rar_entries(archive_path)?
+        .iter()
+        .filter(|entry| !entry.is_directory() && !is_rar_symlink(entry))
+        .filter_map(|entry| sevenz_entry_path(&entry.filename.to_string_lossy()))
+        .collect();
+    let root = find_mod_root(&entry_paths);
     
-    // Get the temp directory for downloads
-    let temp_dir = std::env::temp_dir();
-    let download_path = temp_dir.join(format!("{}.zip", mod_folder_name));
+    Ok(entry_paths.iter().filter_map(|path| zip_entry_relative_path(path, root.as_deref())).collect())
+}
+
+#[cfg(feature = "rar")]
+fn rar_entries(archive_path: &Path) -> Result<Vec<unrar::FileHeader>, AppError> {
+    unrar::Archive::new(archive_path)
+        .open_for_listing()
+        .map_err(|e| AppError::Parse(format!("Failed to read rar archive: {}", e)))?
+        .map(|entry| entry.map_err(|e| AppError::Parse(format!("Failed to read rar archive: {}", e))))
+        .collect()
+}
+
+// Archives made on Unix keep the file mode in the attributes, links are never recreated
+#[cfg(feature = "rar")]
+fn is_rar_symlink(entry: &unrar::FileHeader) -> bool {
+    entry.file_attr & 0o170000 == 0o120000
+}
+
+fn extract_7z(archive_path: &Path, extract_to: &Path, flatten: bool, on_progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<String>, AppError> {
+    use std::io::Read;
     
-    // Download the file
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&download_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download mod: {}", e))?;
+    let mut reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())
+        .map_err(|e| AppError::Parse(format!("Failed to read 7z archive: {}", e)))?;
     
-    if !response.status().is_success() {
-        return Err(format!("Download failed with status: {}", response.status()));
+    let root = if flatten {
+        let entry_paths: Vec<PathBuf> = reader.archive().files.iter().filter_map(|entry| sevenz_entry_path(entry.name())).collect();
+        find_mod_root(&entry_paths)
+    } else {
+        None
+    };
+    if let Some(root) = &root {
+        info!("Flattening archive root folder: {}", root.display());
     }
     
-    let content = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read download content: {}", e))?;
-    
-    // Save to temp file
-    let mut file = fs::File::create(&download_path)
-        .map_err(|e| format!("Failed to create temp file: {}", e))?;
-    
-    file.write_all(&content)
-        .map_err(|e| format!("Failed to write temp file: {}", e))?;
-    
-    // Extract the zip file
-    let mod_path = Path::new(&mods_path).join(&mod_folder_name);
+    fs::create_dir_all(extract_to)
+        .map_err(|e| AppError::Io(format!("Failed to create extraction directory: {}", e)))?;
     
-    // Create backup of existing mod
-    let backup_path = Path::new(&mods_path).join(format!("{}.backup", mod_folder_name));
-    if mod_path.exists() {
-        // Remove old backup if it exists
-        if backup_path.exists() {
-            fs::remove_dir_all(&backup_path)
-                .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+    let files_total = reader.archive().files.len();
+    let mut files_done = 0;
+    let mut skipped = Vec::new();
+    let mut failure = None;
+    let result = reader.for_each_entries(|entry, data: &mut dyn Read| {
+        files_done += 1;
+        on_progress(files_done, files_total);
+        
+        let relative_path = match sevenz_entry_path(entry.name()) {
+            Some(entry_path) => zip_entry_relative_path(&entry_path, root.as_deref()),
+            None => {
+                warn!("Skipping unsafe path in archive: {}", entry.name());
+                skipped.push(entry.name().to_string());
+                None
+            }
+        };
+        
+        // Same as zip, the target of a symlink is never recreated. Unix modes live in the high attribute bits
+        let is_symlink = entry.has_windows_attributes
+            && entry.windows_attributes & 0x8000 != 0
+            && (entry.windows_attributes >> 16) & 0o170000 == 0o120000;
+        if is_symlink {
+            warn!("Skipping symlink in archive: {}", entry.name());
+            skipped.push(entry.name().to_string());
         }
         
-        // Move current mod to backup
-        fs::rename(&mod_path, &backup_path)
-            .map_err(|e| format!("Failed to create backup: {}", e))?;
-    }
-    
-    // Extract new mod
-    extract_zip(&download_path, &mod_path)?;
+        let outpath = match relative_path {
+            Some(relative_path) if !is_symlink && !entry.is_anti_item() => extract_to.join(relative_path),
+            // Solid archives decode sequentially, so skipped data still has to be read
+            _ => {
+                std::io::copy(data, &mut std::io::sink()).map_err(sevenz_rust::Error::io)?;
+                return Ok(true);
+            }
+        };
+        
+        let written = if entry.is_directory() {
+            fs::create_dir_all(&outpath)
+        } else {
+            outpath
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::File::create(&outpath))
+                .and_then(|mut outfile| std::io::copy(data, &mut outfile).map(|_| ()))
+        };
+        
+        if let Err(e) = written {
+            failure = Some(AppError::Io(format!("Failed to extract {}: {}", outpath.display(), e)));
+            return Ok(false);
+        }
+        Ok(true)
+    });
     
-    // Clean up temp file
-    let _ = fs::remove_file(&download_path);
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    result.map_err(|e| AppError::Parse(format!("Failed to extract 7z archive: {}", e)))?;
     
-    // Remove backup if extraction was successful
-    if backup_path.exists() {
-        let _ = fs::remove_dir_all(&backup_path);
+    if !skipped.is_empty() {
+        warn!("Skipped {} unsafe entries while extracting {}", skipped.len(), archive_path.display());
     }
     
-    Ok(format!("Successfully updated mod: {}", mod_folder_name))
+    Ok(skipped)
 }
 
-fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<(), String> {
+fn list_7z_files(archive_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())
+        .map_err(|e| AppError::Parse(format!("Failed to read 7z archive: {}", e)))?;
+    
+    let entry_paths: Vec<PathBuf> = reader
+        .archive()
+        .files
+        .iter()
+        .filter(|entry| !entry.is_directory())
+        .filter_map(|entry| sevenz_entry_path(entry.name()))
+        .collect();
+    let root = find_mod_root(&entry_paths);
     
+    Ok(entry_paths.iter().filter_map(|path| zip_entry_relative_path(path, root.as_deref())).collect())
+}
+
+// Equivalent of enclosed_name for 7z and rar entries, None for absolute paths or ".." anywhere
+fn sevenz_entry_path(name: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(name.replace('\\', "/"));
+    path.components()
+        .all(|component| matches!(component, std::path::Component::Normal(_) | std::path::Component::CurDir))
+        .then_some(path)
+}
+
+fn extract_zip(zip_path: &Path, extract_to: &Path, flatten: bool, on_progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<String>, AppError> {
     let file = fs::File::open(zip_path)
-        .map_err(|e| format!("Failed to open zip file: {}", e))?;
+        .map_err(|e| AppError::Io(format!("Failed to open zip file: {}", e)))?;
     
     let mut archive = zip::ZipArchive::new(file)
-        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Failed to read zip archive: {}", e)))?;
+    
+    // Strip wrapper folders so the manifest ends up at the top of the mod folder
+    let root = if flatten { find_zip_mod_root(&mut archive) } else { None };
+    if let Some(root) = &root {
+        info!("Flattening archive root folder: {}", root.display());
+    }
     
     // Create extraction directory
     fs::create_dir_all(extract_to)
-        .map_err(|e| format!("Failed to create extraction directory: {}", e))?;
+        .map_err(|e| AppError::Io(format!("Failed to create extraction directory: {}", e)))?;
     
-    for i in 0..archive.len() {
+    let mut skipped = Vec::new();
+    let files_total = archive.len();
+    for i in 0..files_total {
+        on_progress(i + 1, files_total);
         let mut file = archive.by_index(i)
-            .map_err(|e| format!("Failed to read zip entry {}: {}", i, e))?;
+            .map_err(|e| AppError::Parse(format!("Failed to read zip entry {}: {}", i, e)))?;
+        
+        // Absolute paths and ".." would land outside the mod folder
+        let entry_path = match file.enclosed_name() {
+            Some(path) => path.to_path_buf(),
+            None => {
+                warn!("Skipping unsafe path in archive: {}", file.name());
+                skipped.push(file.name().to_string());
+                continue;
+            }
+        };
         
-        let outpath = match file.enclosed_name() {
-            Some(path) => extract_to.join(path),
+        let relative_path = match zip_entry_relative_path(&entry_path, root.as_deref()) {
+            Some(path) => path,
             None => continue,
         };
         
+        // enclosed_name already rejects "..", but never write outside the target whatever happens
+        let escapes = relative_path.components().any(|component| !matches!(component, std::path::Component::Normal(_)));
+        let outpath = extract_to.join(&relative_path);
+        if escapes || !outpath.starts_with(extract_to) {
+            return Err(AppError::Invalid(format!("Archive entry escapes the extraction folder: {}", file.name())));
+        }
+        
+        // A symlink could point anywhere on disk, so its target is never recreated
+        if file.unix_mode().is_some_and(|mode| mode & 0o170000 == 0o120000) {
+            warn!("Skipping symlink in archive: {}", file.name());
+            skipped.push(file.name().to_string());
+            continue;
+        }
+        
         if file.name().ends_with('/') {
             // Directory
             fs::create_dir_all(&outpath)
-                .map_err(|e| format!("Failed to create directory: {}", e))?;
+                .map_err(|e| AppError::Io(format!("Failed to create directory: {}", e)))?;
         } else {
             // File
             if let Some(p) = outpath.parent() {
                 if !p.exists() {
-                    fs::create_dir_all(&p)
-                        .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+                    fs::create_dir_all(p)
+                        .map_err(|e| AppError::Io(format!("Failed to create parent directory: {}", e)))?;
                 }
             }
             
             let mut outfile = fs::File::create(&outpath)
-                .map_err(|e| format!("Failed to create output file: {}", e))?;
+                .map_err(|e| AppError::Io(format!("Failed to create output file: {}", e)))?;
             
             std::io::copy(&mut file, &mut outfile)
-                .map_err(|e| format!("Failed to extract file: {}", e))?;
+                .map_err(|e| AppError::Io(format!("Failed to extract file: {}", e)))?;
+            
+            // Keep executable bits from the archive, but always leave the file readable and writable by the owner
+            #[cfg(unix)]
+            if let Some(mode) = file.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&outpath, fs::Permissions::from_mode((mode & 0o777) | 0o600))
+                    .map_err(|e| AppError::Io(format!("Failed to set permissions on {}: {}", outpath.display(), e)))?;
+            }
         }
     }
     
-    Ok(())
+    if !skipped.is_empty() {
+        warn!("Skipped {} unsafe entries while extracting {}", skipped.len(), zip_path.display());
+    }
+    
+    Ok(skipped)
+}
+
+// Where an archive entry lands inside the mod folder, None when it is skipped
+fn zip_entry_relative_path(entry_path: &Path, root: Option<&Path>) -> Option<PathBuf> {
+    let relative_path = match root {
+        Some(root) => match entry_path.strip_prefix(root) {
+            Ok(stripped) => stripped.to_path_buf(),
+            Err(_) => {
+                info!("Skipping archive entry outside of the mod folder: {}", entry_path.display());
+                return None;
+            }
+        },
+        None => entry_path.to_path_buf(),
+    };
+    
+    // The stripped root folder itself
+    if relative_path.as_os_str().is_empty() {
+        None
+    } else {
+        Some(relative_path)
+    }
+}
+
+// Files extract_zip would write, relative to the mod folder
+fn list_zip_files(zip_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let file = fs::File::open(zip_path)
+        .map_err(|e| AppError::Io(format!("Failed to open zip file: {}", e)))?;
+    
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| AppError::Parse(format!("Failed to read zip archive: {}", e)))?;
+    
+    let root = find_zip_mod_root(&mut archive);
+    let mut files = Vec::new();
+    
+    for i in 0..archive.len() {
+        let file = archive.by_index(i)
+            .map_err(|e| AppError::Parse(format!("Failed to read zip entry {}: {}", i, e)))?;
+        
+        if file.name().ends_with('/') {
+            continue;
+        }
+        
+        if let Some(relative_path) = file.enclosed_name().and_then(|path| zip_entry_relative_path(path, root.as_deref())) {
+            files.push(relative_path);
+        }
+    }
+    
+    Ok(files)
+}
+
+fn find_zip_mod_root<R: std::io::Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>) -> Option<PathBuf> {
+    let entry_paths: Vec<PathBuf> = (0..archive.len())
+        .filter_map(|i| archive.by_index(i).ok()?.enclosed_name().map(Path::to_path_buf))
+        .collect();
+    find_mod_root(&entry_paths)
+}
+
+// Folder to flatten an archive from, given the paths of its entries
+fn find_mod_root(entry_paths: &[PathBuf]) -> Option<PathBuf> {
+    // Collect the folders containing a manifest
+    let manifest_dirs: Vec<PathBuf> = entry_paths
+        .iter()
+        .filter(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().eq_ignore_ascii_case("manifest.json"))
+                .unwrap_or(false)
+        })
+        .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default())
+        .collect();
+    
+    // Only the shallowest manifests matter, deeper ones belong to bundled content packs
+    let min_depth = manifest_dirs.iter().map(|dir| dir.components().count()).min()?;
+    let top_dirs: Vec<&PathBuf> = manifest_dirs
+        .iter()
+        .filter(|dir| dir.components().count() == min_depth)
+        .collect();
+    
+    // Several mods side by side share their parent folder, a single mod is its own root
+    let root = if top_dirs.len() == 1 {
+        top_dirs[0].clone()
+    } else {
+        let mut common = top_dirs[0].parent().map(Path::to_path_buf).unwrap_or_default();
+        while !top_dirs.iter().all(|dir| dir.starts_with(&common)) {
+            if !common.pop() {
+                break;
+            }
+        }
+        common
+    };
+    
+    if root.as_os_str().is_empty() {
+        None
+    } else {
+        Some(root)
+    }
+}
+
+fn get_config_dir() -> Result<PathBuf, AppError> {
+    match CONFIG_DIR.get() {
+        Some(config_dir) => Ok(config_dir.clone()),
+        None => portable_config_dir().map_or_else(legacy_config_dir, Ok),
+    }
+}
+
+// Set when the marker file sits beside the executable, e.g. when running off a USB stick
+fn portable_config_dir() -> Option<PathBuf> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    exe_dir.join(PORTABLE_MARKER).is_file().then_some(exe_dir)
 }
 
-fn get_settings_path() -> Result<PathBuf, String> {
+// Location used before switching to Tauri's path resolver, still the fallback when it's unavailable
+fn legacy_config_dir() -> Result<PathBuf, AppError> {
     let config_dir = if cfg!(target_os = "macos") {
         env::var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
     } else if cfg!(target_os = "windows") {
         env::var("APPDATA").map(PathBuf::from)
     } else {
         env::var("HOME").map(|home| PathBuf::from(home).join(".config"))
-    }.map_err(|_| "Failed to get config directory")?;
+    }.map_err(|_| AppError::Io("Failed to get config directory".to_string()))?;
+    
+    Ok(config_dir.join("stardew-mod-manager"))
+}
+
+// Runs before logging is set up, so the caller logs the outcome afterwards
+fn init_config_dir(app: &tauri::App) -> Result<(), tauri::Error> {
+    if let Some(portable_dir) = portable_config_dir() {
+        let _ = CONFIG_DIR.set(portable_dir);
+        return Ok(());
+    }
+    
+    let _ = CONFIG_DIR.set(app.path().app_config_dir()?);
+    Ok(())
+}
+
+fn migrate_legacy_config() {
+    let (Some(config_dir), Ok(legacy_dir)) = (CONFIG_DIR.get(), legacy_config_dir()) else {
+        return;
+    };
+    
+    // Only migrate once, settings in the new location always win. Portable installs start clean
+    if *config_dir == legacy_dir || config_dir.join("settings.json").exists() || portable_config_dir().is_some() {
+        return;
+    }
+    
+    for file_name in ["settings.json", "profiles.json", "update_cache.json"] {
+        let legacy_path = legacy_dir.join(file_name);
+        if !legacy_path.is_file() {
+            continue;
+        }
+        
+        let result = fs::create_dir_all(config_dir).and_then(|_| fs::copy(&legacy_path, config_dir.join(file_name)));
+        match result {
+            Ok(_) => info!("Migrated {} from {}", file_name, legacy_dir.display()),
+            Err(e) => error!("Error migrating {} from {}: {}", file_name, legacy_dir.display(), e),
+        }
+    }
+}
+
+// Writes next to the target and renames over it, so a crash never leaves a half-written file
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    
+    let result = fs::File::create(&temp_path)
+        .and_then(|mut file| file.write_all(contents).and_then(|_| file.sync_all()))
+        .and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn get_settings_path() -> Result<PathBuf, AppError> {
+    Ok(get_config_dir()?.join("settings.json"))
+}
+
+fn get_smapi_log_path() -> Result<PathBuf, AppError> {
+    // SMAPI writes its logs to the game's own data folder
+    let data_dir = if cfg!(target_os = "windows") {
+        env::var("APPDATA").map(PathBuf::from)
+    } else {
+        env::var("HOME").map(|home| PathBuf::from(home).join(".config"))
+    }.map_err(|_| AppError::Io("Failed to get the Stardew Valley data directory".to_string()))?;
+    
+    Ok(data_dir.join("StardewValley").join("ErrorLogs").join("SMAPI-latest.txt"))
+}
+
+fn get_profiles_path() -> Result<PathBuf, AppError> {
+    Ok(get_config_dir()?.join("profiles.json"))
+}
+
+fn load_profiles() -> Result<ProfilesFile, AppError> {
+    let profiles_path = get_profiles_path()?;
+    
+    if !profiles_path.exists() {
+        return Ok(ProfilesFile::default());
+    }
+    
+    let content = fs::read_to_string(&profiles_path)
+        .map_err(|e| AppError::Io(format!("Failed to read profiles: {}", e)))?;
+    serde_json::from_str(&content).map_err(|e| AppError::Parse(format!("Failed to parse profiles: {}", e)))
+}
+
+fn save_profiles(profiles_file: &ProfilesFile) -> Result<(), AppError> {
+    let profiles_path = get_profiles_path()?;
+    
+    if let Some(parent) = profiles_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create profiles directory: {}", e)))?;
+    }
+    
+    let json = serde_json::to_string_pretty(profiles_file).map_err(|e| AppError::Parse(format!("Failed to serialize profiles: {}", e)))?;
+    fs::write(&profiles_path, json).map_err(|e| AppError::Io(format!("Failed to write profiles: {}", e)))
+}
+
+fn get_update_cache_path() -> Result<PathBuf, AppError> {
+    Ok(get_config_dir()?.join("update_cache.json"))
+}
+
+fn load_update_cache() -> UpdateCache {
+    let cache_path = match get_update_cache_path() {
+        Ok(path) => path,
+        Err(_) => return UpdateCache::default(),
+    };
+    
+    match fs::read_to_string(&cache_path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            error!("Error parsing update cache: {}", e);
+            UpdateCache::default()
+        }),
+        Err(_) => UpdateCache::default(),
+    }
+}
+
+fn save_update_cache(cache: &UpdateCache) -> Result<(), AppError> {
+    let cache_path = get_update_cache_path()?;
+    
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Io(format!("Failed to create cache directory: {}", e)))?;
+    }
     
-    Ok(config_dir.join("stardew-mod-manager").join("settings.json"))
+    let json = serde_json::to_string_pretty(cache).map_err(|e| AppError::Parse(format!("Failed to serialize update cache: {}", e)))?;
+    fs::write(&cache_path, json).map_err(|e| AppError::Io(format!("Failed to write update cache: {}", e)))
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
-async fn check_single_mod_update(mod_info: &ModInfo) -> Result<UpdateInfo, String> {
-    println!("Checking updates for mod: {} ({})", mod_info.name, mod_info.version);
-    println!("Update keys: {:?}", mod_info.update_keys);
+async fn check_single_mod_update(mod_info: &ModInfo, settings: &AppSettings, cache: &Mutex<UpdateCache>, rate_limit: &Mutex<NexusRateLimit>, lookups: &UpdateKeyLookups, force: bool) -> Result<UpdateInfo, AppError> {
+    debug!("Checking updates for mod: {} ({})", mod_info.name, mod_info.version);
+    debug!("Update keys: {:?}", mod_info.update_keys);
+    
+    // A pin only holds while the mod is still on that version
+    let pinned = settings.pinned_versions.get(&enabled_folder_name(&mod_info.folder_name));
+    if pinned.is_some_and(|version| normalize_version(version) == normalize_version(&mod_info.version)) {
+        debug!("{} is pinned at {}, skipping update check", mod_info.name, mod_info.version);
+        return Ok(UpdateInfo::unchecked(&mod_info.version, UpdateStatus::Pinned));
+    }
+    
+    if settings.offline && !mod_info.update_keys.is_empty() {
+        debug!("Offline mode, skipping update check for {}", mod_info.name);
+        return Ok(UpdateInfo::unchecked(&mod_info.version, UpdateStatus::Offline));
+    }
     
-    // Get settings for API key
-    let settings = get_settings().unwrap_or_else(|_| AppSettings { nexus_api_key: None });
+    let mut rate_limited = false;
+    let mut manual_check = None;
+    let mut failed_checks = Vec::new();
     
     for update_key in &mod_info.update_keys {
-        println!("Checking update key: {}", update_key);
-        match check_update_key(update_key, &mod_info.version, &settings).await {
-            Ok(update_info) => {
-                println!("Update check successful for {}: {} -> {}", mod_info.name, update_info.current_version, update_info.latest_version);
+        // "Nexus: 1234" and "nexus:1234" are the same key
+        let cache_key = match parse_update_key(update_key) {
+            Some((provider, id, Some(subkey))) => format!("{}:{}@{}", provider, id, subkey).to_lowercase(),
+            Some((provider, id, None)) => format!("{}:{}", provider, id).to_lowercase(),
+            None => update_key.trim().to_lowercase(),
+        };
+        
+        // Reuse a recent result for this update key unless a refresh was requested
+        if !force {
+            let cached = cache.lock().unwrap().entries.get(&cache_key).cloned();
+            if let Some(cached) = cached {
+                if unix_timestamp().saturating_sub(cached.checked_at) < settings.update_cache_ttl_secs {
+                    debug!("Using cached result for update key {}: {}", update_key, cached.latest_version);
+                    let update_available = version_compare(&mod_info.version, &cached.latest_version);
+                    return Ok(UpdateInfo {
+                        current_version: mod_info.version.clone(),
+                        latest_version: cached.latest_version.clone(),
+                        update_available,
+                        download_url: cached.download_url.clone(),
+                        update_status: UpdateStatus::from_update_available(update_available),
+                        checked_via: update_key.trim().to_string(),
+                        failed_checks,
+                        rate_limit_reset: None,
+                    });
+                }
+            }
+        }
+        
+        // Mods sharing an update key reuse the lookup, even while it's still in flight
+        let lookup = lookups.lock().unwrap().entry(cache_key.clone()).or_default().clone();
+        let result = lookup
+            .get_or_init(|| async {
+                debug!("Checking update key: {}", update_key);
+                check_update_key(update_key, &mod_info.version, settings, rate_limit).await
+            })
+            .await
+            .clone()
+            .map(|update_info| update_info.for_version(&mod_info.version));
+        
+        match result {
+            Ok(mut update_info) => {
+                update_info.checked_via = update_key.trim().to_string();
+                
+                debug!("Update check successful for {}: {} -> {}", mod_info.name, update_info.current_version, update_info.latest_version);
+                
+                // Manual checks have no real version to remember, and another key may still give one
+                if update_info.update_status == UpdateStatus::ManualCheck {
+                    if manual_check.is_none() {
+                        manual_check = Some(update_info);
+                    }
+                    continue;
+                }
+                
+                cache.lock().unwrap().entries.insert(cache_key, CachedUpdate {
+                    latest_version: update_info.latest_version.clone(),
+                    download_url: update_info.download_url.clone(),
+                    checked_at: unix_timestamp(),
+                });
+                
+                update_info.failed_checks = failed_checks;
                 return Ok(update_info);
             }
             Err(e) => {
-                println!("Update check failed for {} with key {}: {}", mod_info.name, update_key, e);
+                debug!("Update check failed for {} with key {}: {}", mod_info.name, update_key, e);
+                failed_checks.push(format!("{}: {}", update_key.trim(), e.message()));
+                if cache_key.starts_with("nexus:") && rate_limit.lock().unwrap().exhausted {
+                    rate_limited = true;
+                }
                 continue;
             }
         }
     }
     
-    // No updates found or all checks failed
-    println!("No update keys worked for mod: {}", mod_info.name);
+    // Report the rate limit instead of pretending the mod is up to date
+    if rate_limited {
+        return Err(rate_limit.lock().unwrap().error());
+    }
+    
+    if let Some(mut update_info) = manual_check {
+        update_info.failed_checks = failed_checks;
+        return Ok(update_info);
+    }
+    
+    if mod_info.update_keys.is_empty() {
+        debug!("No update keys for mod: {}", mod_info.name);
+        return Ok(UpdateInfo::unchecked(&mod_info.version, UpdateStatus::NoUpdateKey));
+    }
+    
+    // All checks failed
+    debug!("No update keys worked for mod: {}", mod_info.name);
     Ok(UpdateInfo {
-        current_version: mod_info.version.clone(),
-        latest_version: mod_info.version.clone(),
-        update_available: false,
-        download_url: None,
+        failed_checks,
+        ..UpdateInfo::unchecked(&mod_info.version, UpdateStatus::Error)
     })
 }
 
-async fn check_update_key(update_key: &str, current_version: &str, settings: &AppSettings) -> Result<UpdateInfo, String> {
-    let key_lower = update_key.to_lowercase();
-    if key_lower.starts_with("nexus:") {
-        let mod_id = update_key[6..].trim(); // Skip "nexus:" and trim whitespace
-        check_nexus_update(mod_id, current_version, settings).await
-    } else if key_lower.starts_with("github:") {
-        let repo = update_key[7..].trim(); // Skip "github:" and trim whitespace
-        check_github_update(repo, current_version).await
-    } else {
-        Err(format!("Unsupported update key format: {}", update_key))
+async fn check_update_key(update_key: &str, current_version: &str, settings: &AppSettings, rate_limit: &Mutex<NexusRateLimit>) -> Result<UpdateInfo, AppError> {
+    let key_lower = update_key.trim().to_lowercase();
+    if key_lower.starts_with("http://") || key_lower.starts_with("https://") {
+        debug!("Update key {} is a direct URL, manual check required", update_key);
+        return Ok(UpdateInfo::manual_check(current_version, Some(update_key.trim().to_string())));
+    }
+    
+    match parse_update_key(update_key) {
+        Some((provider, mod_id, _)) if provider == "nexus" => check_nexus_update(mod_id, current_version, settings, rate_limit).await,
+        // The subkey picks the version out of the tag
+        Some((provider, repo, subkey)) if provider == "github" => {
+            check_github_update(repo, subkey, current_version, settings).await
+        }
+        Some((provider, mod_id, _)) if provider == "chucklefish" => {
+            // The Chucklefish forums have no API, the best we can do is point at the mod page
+            debug!("Chucklefish mod {}: manual check required", mod_id);
+            Ok(UpdateInfo::manual_check(current_version, Some(format!("https://community.playstarbound.com/resources/{}", mod_id))))
+        }
+        Some((provider, mod_id, _)) if provider == "moddrop" => {
+            // ModDrop's API isn't public, same as Chucklefish
+            debug!("ModDrop mod {}: manual check required", mod_id);
+            Ok(UpdateInfo::manual_check(current_version, update_key_url(update_key)))
+        }
+        _ => {
+            // Unknown providers shouldn't fail the whole check, the user can still look it up
+            debug!("Unsupported update key format: {}, manual check required", update_key);
+            Ok(UpdateInfo::manual_check(current_version, None))
+        }
+    }
+}
+
+// Splits "Provider:ID@subkey" into the lowercased provider, the ID and the optional subkey.
+// SMAPI is lenient about spacing and casing, e.g. "Nexus: 1234" or "GitHub:Owner/Repo@v#"
+fn parse_update_key(update_key: &str) -> Option<(String, &str, Option<&str>)> {
+    let (provider, rest) = update_key.split_once(':')?;
+    let (id, subkey) = match rest.split_once('@') {
+        Some((id, subkey)) => (id.trim(), Some(subkey.trim()).filter(|subkey| !subkey.is_empty())),
+        None => (rest.trim(), None),
+    };
+    
+    let provider = provider.trim().to_lowercase();
+    if provider.is_empty() || id.is_empty() {
+        return None;
     }
+    
+    Some((provider, id, subkey))
 }
 
-async fn check_nexus_update(mod_id: &str, current_version: &str, settings: &AppSettings) -> Result<UpdateInfo, String> {
+async fn check_nexus_update(mod_id: &str, current_version: &str, settings: &AppSettings, rate_limit: &Mutex<NexusRateLimit>) -> Result<UpdateInfo, AppError> {
     let mod_page_url = format!("https://www.nexusmods.com/stardewvalley/mods/{}", mod_id);
     
     // Check if we have an API key
     if let Some(api_key) = &settings.nexus_api_key {
         if !api_key.trim().is_empty() {
-            println!("Nexus mod {}: Checking with API", mod_id);
-            return check_nexus_with_api(mod_id, current_version, api_key, &mod_page_url).await;
+            debug!("Nexus mod {}: Checking with API", mod_id);
+            return check_nexus_with_api(mod_id, current_version, api_key, &mod_page_url, rate_limit, settings).await;
         }
     }
     
     // No API key available, provide manual check
-    println!("Nexus mod {}: No API key configured, manual check required", mod_id);
+    debug!("Nexus mod {}: No API key configured, manual check required", mod_id);
+    Ok(UpdateInfo::manual_check(current_version, Some(mod_page_url)))
+}
+
+async fn check_nexus_with_api(mod_id: &str, current_version: &str, api_key: &str, mod_page_url: &str, rate_limit: &Mutex<NexusRateLimit>, settings: &AppSettings) -> Result<UpdateInfo, AppError> {
+    let mod_info: NexusModInfo = nexus_api_get(&format!("games/stardewvalley/mods/{}", mod_id), api_key, rate_limit, settings)
+        .await
+        .map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound(format!("Mod {} not found on Nexus", mod_id)),
+            e => e,
+        })?;
+    
+    let latest_version = &mod_info.version;
+    let update_available = version_compare(current_version, latest_version);
+    
+    debug!("Nexus mod {}: API returned version {} (current: {})", mod_id, latest_version, current_version);
+    
     Ok(UpdateInfo {
         current_version: current_version.to_string(),
-        latest_version: "Manual check".to_string(),
-        update_available: false,
-        download_url: Some(mod_page_url),
+        latest_version: latest_version.to_string(),
+        update_available,
+        download_url: Some(mod_page_url.to_string()),
+        update_status: UpdateStatus::from_update_available(update_available),
+        checked_via: String::new(),
+        failed_checks: Vec::new(),
+        rate_limit_reset: None,
     })
 }
 
-async fn check_nexus_with_api(mod_id: &str, current_version: &str, api_key: &str, mod_page_url: &str) -> Result<UpdateInfo, String> {
-    let client = reqwest::Client::new();
-    let api_url = format!("https://api.nexusmods.com/v1/games/stardewvalley/mods/{}", mod_id);
+// GET an endpoint of the Nexus API, e.g. "games/stardewvalley/mods/123"
+async fn nexus_api_get<T: serde::de::DeserializeOwned>(endpoint: &str, api_key: &str, rate_limit: &Mutex<NexusRateLimit>, settings: &AppSettings) -> Result<T, AppError> {
+    nexus_api_get_with_query(endpoint, &[], api_key, rate_limit, settings).await
+}
+
+// Query values are encoded by reqwest, they may come straight from an nxm:// link
+async fn nexus_api_get_with_query<T: serde::de::DeserializeOwned>(endpoint: &str, query: &[(&str, &str)], api_key: &str, rate_limit: &Mutex<NexusRateLimit>, settings: &AppSettings) -> Result<T, AppError> {
+    // Don't keep hitting Nexus once the limit for this batch is used up
+    if let Some(e) = rate_limit.lock().unwrap().exhausted_error() {
+        return Err(e);
+    }
+    
+    let client = http_client(settings)?;
+    let api_url = format!("https://api.nexusmods.com/v1/{}", endpoint);
     
-    let response = client
+    let request = client
         .get(&api_url)
+        .query(query)
+        .timeout(request_timeout(settings))
         .header("apikey", api_key)
         .header("User-Agent", "stardew-mod-manager/1.0")
         .header("Application-Name", "Stardew Valley Mod Manager")
-        .header("Application-Version", "1.0")
-        .send()
+        .header("Application-Version", "1.0");
+    let response = send_with_retry(request, settings.max_retries)
         .await
-        .map_err(|e| format!("Failed to fetch from Nexus API: {}", e))?;
+        .map_err(|e| network_error(settings, "Failed to fetch from Nexus API", e))?;
+    
+    update_nexus_rate_limit(&mut rate_limit.lock().unwrap(), response.headers());
     
     if !response.status().is_success() {
-        if response.status() == 401 {
-            return Err("Invalid Nexus API key".to_string());
+        if response.status() == 429 {
+            let mut rate_limit = rate_limit.lock().unwrap();
+            rate_limit.exhausted = true;
+            return Err(rate_limit.error());
+        } else if response.status() == 401 {
+            return Err(AppError::Auth("Invalid Nexus API key".to_string()));
         } else if response.status() == 404 {
-            return Err(format!("Mod {} not found on Nexus", mod_id));
+            return Err(AppError::NotFound(format!("Nexus API resource not found: {}", endpoint)));
         } else {
-            return Err(format!("Nexus API returned status: {}", response.status()));
+            return Err(AppError::Network(format!("Nexus API returned status: {}", response.status())));
         }
     }
     
-    let mod_info: NexusModInfo = response
+    response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse Nexus API response: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Failed to parse Nexus API response: {}", e)))
+}
+
+// Client for every outgoing request, a dead host fails after the configured timeout
+fn http_client(settings: &AppSettings) -> Result<reqwest::Client, AppError> {
+    if settings.offline {
+        return Err(AppError::Network("Offline mode is enabled".to_string()));
+    }
     
-    let latest_version = &mod_info.version;
-    let update_available = version_compare(current_version, latest_version);
+    let config = HttpClientConfig {
+        timeout_secs: settings.request_timeout_secs,
+        proxy_url: proxy_url(settings).map(str::to_string),
+    };
+    
+    let mut shared = HTTP_CLIENT.lock().unwrap();
+    if let Some((cached_config, client)) = shared.as_ref() {
+        if *cached_config == config {
+            return Ok(client.clone());
+        }
+    }
+    
+    let mut builder = reqwest::Client::builder()
+        .user_agent("stardew-mod-manager")
+        .connect_timeout(request_timeout(settings));
+    if let Some(proxy_url) = &config.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| AppError::Invalid(format!("Invalid proxy URL {}: {}", proxy_url, e)))?;
+        builder = builder.proxy(proxy);
+    }
+    
+    let client = builder
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    debug!("Created HTTP client (timeout {}s, proxy {:?})", config.timeout_secs, config.proxy_url);
+    
+    *shared = Some((config, client.clone()));
+    Ok(client)
+}
+
+fn proxy_url(settings: &AppSettings) -> Option<&str> {
+    settings.proxy_url.as_deref().map(str::trim).filter(|url| !url.is_empty())
+}
+
+// With a proxy configured, a connection failure most likely comes from the proxy rather than the mod host
+fn network_error(settings: &AppSettings, context: &str, e: reqwest::Error) -> AppError {
+    match proxy_url(settings) {
+        Some(proxy_url) if e.is_connect() => AppError::Network(format!("{}: could not connect through the proxy {} ({})", context, proxy_url, e)),
+        _ => AppError::Network(format!("{}: {}", context, e)),
+    }
+}
+
+fn request_timeout(settings: &AppSettings) -> std::time::Duration {
+    std::time::Duration::from_secs(settings.request_timeout_secs.max(1))
+}
+
+// Retries connection failures and server errors with exponential backoff.
+// Client errors like 401 or 404 won't change on retry, so they are returned right away.
+async fn send_with_retry(request: reqwest::RequestBuilder, max_retries: u32) -> Result<reqwest::Response, reqwest::Error> {
+    let mut attempt = 0;
+    
+    loop {
+        // Streaming bodies can't be cloned, those requests only get one attempt
+        let retry_request = if attempt < max_retries { request.try_clone() } else { None };
+        let current = match retry_request {
+            Some(retry_request) => retry_request,
+            None => return request.send().await,
+        };
+        
+        match current.send().await {
+            Ok(response) if response.status().is_server_error() => {
+                warn!("Request returned {}, retrying (attempt {}/{})", response.status(), attempt + 1, max_retries);
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_connect() || e.is_timeout() => {
+                warn!("Request failed: {}, retrying (attempt {}/{})", e, attempt + 1, max_retries);
+            }
+            Err(e) => return Err(e),
+        }
+        
+        let delay = RETRY_BASE_DELAY_MS.saturating_mul(2u64.saturating_pow(attempt));
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        attempt += 1;
+    }
+}
+
+fn update_nexus_rate_limit(rate_limit: &mut NexusRateLimit, headers: &reqwest::header::HeaderMap) {
+    let header_value = |name: &str| {
+        headers.get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+    };
     
-    println!("Nexus mod {}: API returned version {} (current: {})", mod_id, latest_version, current_version);
+    if let Some(reset) = header_value("X-RL-Hourly-Reset") {
+        rate_limit.hourly_reset = Some(reset);
+    }
+    
+    if let Some(remaining) = header_value("X-RL-Hourly-Remaining").and_then(|v| v.parse::<u32>().ok()) {
+        debug!("Nexus API hourly requests remaining: {}", remaining);
+        rate_limit.hourly_remaining = Some(remaining);
+        if remaining == 0 {
+            rate_limit.exhausted = true;
+        }
+    }
+}
+
+async fn check_github_update(repo: &str, subkey: Option<&str>, current_version: &str, settings: &AppSettings) -> Result<UpdateInfo, AppError> {
+    let (release, latest_version) = fetch_github_release(repo, subkey, settings).await?;
+    let update_available = version_compare(current_version, &latest_version);
     
     Ok(UpdateInfo {
         current_version: current_version.to_string(),
-        latest_version: latest_version.to_string(),
+        latest_version,
         update_available,
-        download_url: Some(mod_page_url.to_string()),
+        download_url: Some(release.html_url),
+        update_status: UpdateStatus::from_update_available(update_available),
+        checked_via: String::new(),
+        failed_checks: Vec::new(),
+        rate_limit_reset: None,
     })
 }
 
-async fn check_github_update(repo: &str, current_version: &str) -> Result<UpdateInfo, String> {
-    let client = reqwest::Client::new();
-    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+// Latest release of a repository along with the version read from its tag
+async fn fetch_github_release(repo: &str, subkey: Option<&str>, settings: &AppSettings) -> Result<(GitHubRelease, String), AppError> {
+    let client = http_client(settings)?;
+    // releases/latest never returns prereleases, so the full list is needed to include them
+    let url = if settings.include_prereleases {
+        format!("https://api.github.com/repos/{}/releases", repo)
+    } else {
+        format!("https://api.github.com/repos/{}/releases/latest", repo)
+    };
     
-    let response = client
+    let request = client
         .get(&url)
-        .header("User-Agent", "stardew-mod-manager")
-        .send()
+        .timeout(request_timeout(settings));
+    let response = send_with_retry(request, settings.max_retries)
         .await
-        .map_err(|e| format!("Failed to fetch GitHub release: {}", e))?;
+        .map_err(|e| network_error(settings, "Failed to fetch GitHub release", e))?;
     
     if !response.status().is_success() {
-        return Err(format!("GitHub API returned status: {}", response.status()));
+        return Err(AppError::Network(format!("GitHub API returned status: {}", response.status())));
     }
     
-    let release: GitHubRelease = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+    if settings.include_prereleases {
+        let releases: Vec<GitHubRelease> = response
+            .json()
+            .await
+            .map_err(|e| AppError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+        
+        // Newest published release whose tag matches the subkey, ISO 8601 dates sort as strings
+        releases
+            .into_iter()
+            .filter(|release| !release.draft)
+            .filter_map(|release| extract_tag_version(&release.tag_name, subkey).map(|version| (release, version)))
+            .max_by(|(a, _), (b, _)| a.published_at.cmp(&b.published_at))
+            .ok_or_else(|| AppError::NotFound(format!("No GitHub release of {} matches subkey {}", repo, subkey.unwrap_or_default())))
+    } else {
+        let release: GitHubRelease = response
+            .json()
+            .await
+            .map_err(|e| AppError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+        
+        let latest_version = extract_tag_version(&release.tag_name, subkey).ok_or_else(|| {
+            AppError::Parse(format!("Could not find a version in GitHub tag {} using subkey {}", release.tag_name, subkey.unwrap_or_default()))
+        })?;
+        Ok((release, latest_version))
+    }
+}
+
+fn extract_tag_version(tag: &str, subkey: Option<&str>) -> Option<String> {
+    let subkey = match subkey {
+        Some(subkey) => subkey,
+        // Without a subkey keep the usual "v1.2.3" handling
+        None => return Some(tag.trim_start_matches('v').to_string()),
+    };
     
-    let latest_version = release.tag_name.trim_start_matches('v');
-    let update_available = version_compare(current_version, latest_version);
+    // Tags like "release-1.2.3" or "MyMod-v1.2.3" with the subkey as prefix
+    if let Some(rest) = tag.strip_prefix(subkey) {
+        let version = rest.trim_start_matches(['-', '_', ' ']).trim_start_matches(['v', 'V']);
+        return Some(version.to_string()).filter(|v| !v.is_empty());
+    }
     
-    Ok(UpdateInfo {
-        current_version: current_version.to_string(),
-        latest_version: latest_version.to_string(),
-        update_available,
-        download_url: Some(release.html_url),
-    })
+    // Otherwise treat the subkey as a regex, preferring its first capture group
+    use regex::Regex;
+    let version_re = Regex::new(subkey).ok()?;
+    let caps = version_re.captures(tag)?;
+    let version = caps.get(1).or_else(|| caps.get(0))?.as_str();
+    Some(version.trim_start_matches(['v', 'V']).to_string()).filter(|v| !v.is_empty())
 }
 
 fn version_compare(current: &str, latest: &str) -> bool {
-    println!("Version compare: '{}' vs '{}' (current vs latest)", current, latest);
+    debug!("Version compare: '{}' vs '{}' (current vs latest)", current, latest);
     
+    let current = &normalize_version(current);
+    let latest = &normalize_version(latest);
     if current == latest {
-        println!("  -> Same version, no update needed");
+        debug!("  -> Same version, no update needed");
         return false;
     }
     
+    // Use semantic versioning when possible so prerelease tags are ordered correctly
+    if let (Some(current_semver), Some(latest_semver)) = (parse_semver(current), parse_semver(latest)) {
+        let update_available = latest_semver > current_semver;
+        debug!("  -> Semver comparison: {} vs {}, update available: {}", current_semver, latest_semver, update_available);
+        return update_available;
+    }
+    
     let current_parts: Vec<u32> = current.split('.').filter_map(|s| s.parse().ok()).collect();
     let latest_parts: Vec<u32> = latest.split('.').filter_map(|s| s.parse().ok()).collect();
     
-    println!("  -> Current parts: {:?}, Latest parts: {:?}", current_parts, latest_parts);
+    debug!("  -> Current parts: {:?}, Latest parts: {:?}", current_parts, latest_parts);
     
     let max_len = current_parts.len().max(latest_parts.len());
     
@@ -591,18 +5880,56 @@ fn version_compare(current: &str, latest: &str) -> bool {
         let latest_part = latest_parts.get(i).unwrap_or(&0);
         
         if latest_part > current_part {
-            println!("  -> Update available (latest {} > current {})", latest_part, current_part);
+            debug!("  -> Update available (latest {} > current {})", latest_part, current_part);
             return true;
         } else if latest_part < current_part {
-            println!("  -> Local version is newer (latest {} < current {})", latest_part, current_part);
+            debug!("  -> Local version is newer (latest {} < current {})", latest_part, current_part);
             return false;
         }
     }
     
-    println!("  -> Versions are equivalent, no update needed");
+    debug!("  -> Versions are equivalent, no update needed");
     false
 }
 
+// Versions are compared without a leading "v", without "+build" metadata, without
+// leading zeros and without trailing ".0" parts, so "v0.9.0+5", "0.9.0" and "0.09" are all "0.9".
+// Prerelease tags like "-beta" are kept since they do order versions.
+fn normalize_version(version: &str) -> String {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let version = version.split('+').next().unwrap_or(version);
+    
+    let suffix_start = version.find('-').unwrap_or(version.len());
+    let (core, suffix) = version.split_at(suffix_start);
+    let mut parts: Vec<String> = core
+        .split('.')
+        .map(|part| part.parse::<u64>().map(|number| number.to_string()).unwrap_or_else(|_| part.to_string()))
+        .collect();
+    while parts.len() > 1 && parts.last().is_some_and(|part| part == "0") {
+        parts.pop();
+    }
+    
+    format!("{}{}", parts.join("."), suffix)
+}
+
+fn parse_semver(version: &str) -> Option<semver::Version> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    
+    // SMAPI allows versions like "1.2" or "1.2-beta", pad the numeric core to three parts
+    let suffix_start = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(suffix_start);
+    let mut parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    if parts.len() > 3 {
+        return None;
+    }
+    parts.resize(3, 0);
+    
+    semver::Version::parse(&format!("{}.{}.{}{}", parts[0], parts[1], parts[2], suffix)).ok()
+}
+
 fn get_stardew_paths() -> Vec<PathBuf> {
     let mut paths = Vec::new();
     
@@ -614,6 +5941,32 @@ fn get_stardew_paths() -> Vec<PathBuf> {
         if let Some(program_files) = std::env::var_os("PROGRAMFILES") {
             paths.push(PathBuf::from(program_files).join("Steam/steamapps/common/Stardew Valley"));
         }
+        
+        // GOG Galaxy, the registry knows about installs outside the default folder
+        if let Some(gog_path) = query_registry_value("HKEY_LOCAL_MACHINE\\SOFTWARE\\WOW6432Node\\GOG.com\\Games\\1453375253", "path") {
+            paths.push(PathBuf::from(gog_path));
+        }
+        if let Some(program_files) = std::env::var_os("PROGRAMFILES(X86)") {
+            paths.push(PathBuf::from(program_files).join("GOG Galaxy/Games/Stardew Valley"));
+        }
+        
+        // Xbox app, newer versions install to XboxGames on any drive
+        for drive in 'C'..='Z' {
+            let candidate = PathBuf::from(format!("{}:\\XboxGames\\Stardew Valley\\Content", drive));
+            if candidate.is_dir() {
+                paths.push(candidate);
+            }
+        }
+        if let Some(program_files) = std::env::var_os("PROGRAMFILES") {
+            // Listing WindowsApps usually needs admin rights, so this only finds anything on relaxed setups
+            if let Ok(entries) = fs::read_dir(PathBuf::from(program_files).join("WindowsApps")) {
+                for entry in entries.flatten() {
+                    if entry.file_name().to_string_lossy().starts_with("ConcernedApe.StardewValleyPC") {
+                        paths.push(entry.path());
+                    }
+                }
+            }
+        }
     }
     
     #[cfg(target_os = "macos")]
@@ -639,33 +5992,256 @@ fn get_stardew_paths() -> Vec<PathBuf> {
         }
     }
     
+    // Games installed on a second drive or in a custom Steam library
+    for library in get_steam_library_folders() {
+        let candidate = library.join("steamapps").join("common").join("Stardew Valley");
+        if !paths.contains(&candidate) {
+            paths.push(candidate);
+        }
+    }
+    
     paths
 }
 
+fn get_steam_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(steam_path) = get_steam_path_windows() {
+            roots.push(steam_path);
+        }
+        for var in ["PROGRAMFILES(X86)", "PROGRAMFILES"] {
+            if let Some(program_files) = std::env::var_os(var) {
+                roots.push(PathBuf::from(program_files).join("Steam"));
+            }
+        }
+    }
+    
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            roots.push(PathBuf::from(&home).join("Library/Application Support/Steam"));
+        }
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            roots.push(PathBuf::from(&home).join(".steam/steam"));
+            roots.push(PathBuf::from(&home).join(".local/share/Steam"));
+            // Flatpak Steam
+            roots.push(PathBuf::from(&home).join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
+        }
+    }
+    
+    roots
+}
+
+fn get_steam_library_folders() -> Vec<PathBuf> {
+    let mut libraries = Vec::new();
+    
+    for root in get_steam_roots() {
+        // Older Steam clients kept the file in config/
+        let candidates = [
+            root.join("steamapps").join("libraryfolders.vdf"),
+            root.join("config").join("libraryfolders.vdf"),
+        ];
+        
+        for vdf_path in candidates {
+            if let Ok(content) = fs::read_to_string(&vdf_path) {
+                for library in parse_library_folders_vdf(&content) {
+                    if !libraries.contains(&library) {
+                        libraries.push(library);
+                    }
+                }
+            }
+        }
+    }
+    
+    libraries
+}
+
+enum VdfToken {
+    Open,
+    Close,
+    Text(String),
+}
+
+fn tokenize_vdf(content: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                text.push(match escaped {
+                                    'n' => '\n',
+                                    't' => '\t',
+                                    other => other,
+                                });
+                            }
+                        }
+                        _ => text.push(c),
+                    }
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                // Comment until the end of the line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut text = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '{' | '}' | '"') {
+                        break;
+                    }
+                    text.push(next);
+                    chars.next();
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+        }
+    }
+    
+    tokens
+}
+
+fn parse_library_folders_vdf(content: &str) -> Vec<PathBuf> {
+    let tokens = tokenize_vdf(content);
+    let mut libraries = Vec::new();
+    let mut depth: usize = 0;
+    let mut i = 0;
+    
+    while i < tokens.len() {
+        match &tokens[i] {
+            VdfToken::Open => depth += 1,
+            VdfToken::Close => depth = depth.saturating_sub(1),
+            VdfToken::Text(key) => {
+                if let Some(VdfToken::Text(value)) = tokens.get(i + 1) {
+                    // Current format: "0" { "path" "..." }, old format: "1" "D:\\SteamLibrary" at the top level
+                    let is_library = key.eq_ignore_ascii_case("path") || (depth == 1 && key.parse::<u32>().is_ok());
+                    if is_library && !value.trim().is_empty() {
+                        libraries.push(PathBuf::from(value));
+                    }
+                    i += 2;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+    
+    libraries
+}
+
 #[cfg(target_os = "windows")]
 fn get_steam_path_windows() -> Option<PathBuf> {
+    query_registry_value("HKEY_LOCAL_MACHINE\\SOFTWARE\\Valve\\Steam", "InstallPath").map(PathBuf::from)
+}
+
+#[cfg(target_os = "windows")]
+fn query_registry_value(key: &str, value_name: &str) -> Option<String> {
     use std::process::Command;
     
     let output = Command::new("reg")
-        .args(&["query", "HKEY_LOCAL_MACHINE\\SOFTWARE\\Valve\\Steam", "/v", "InstallPath"])
+        .args(["query", key, "/v", value_name])
         .output()
         .ok()?;
     
-    if output.status.success() {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        if let Some(line) = output_str.lines().find(|line| line.contains("InstallPath")) {
-            if let Some(path_part) = line.split_whitespace().nth(2) {
-                return Some(PathBuf::from(path_part));
-            }
-        }
+    if !output.status.success() {
+        return None;
     }
     
+    // Lines look like "    InstallPath    REG_SZ    C:\\Program Files (x86)\\Steam", the value may contain spaces
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let line = output_str.lines().find(|line| line.trim_start().to_lowercase().starts_with(&value_name.to_lowercase()))?;
+    let (_, value) = line.split_once("REG_SZ")?;
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+// The Xbox app keeps games in protected folders that mod installers often can't write to
+fn install_warning(game_path: &Path) -> Option<String> {
+    let path = game_path.to_string_lossy().to_lowercase();
+    if path.contains("windowsapps") || path.contains("xboxgames") {
+        warn!("{} looks like an Xbox app install", game_path.display());
+        return Some("This is the Xbox app version of the game. Its protected install folder may prevent installing mods".to_string());
+    }
     None
 }
 
+fn find_mods_path(game_path: &Path) -> Option<PathBuf> {
+    // Check different possible Mods folder locations
+    let candidates = [
+        // Standard location
+        game_path.join("Mods"),
+        // macOS Steam version
+        game_path.join("Contents").join("MacOS").join("Mods"),
+        game_path.join("Contents").join("Resources").join("Mods"),
+    ];
+    
+    candidates.into_iter().find(|path| path.exists())
+}
+
+fn find_smapi_executable(game_path: &Path) -> Option<PathBuf> {
+    let executable_name = if cfg!(target_os = "windows") {
+        "StardewModdingAPI.exe"
+    } else {
+        "StardewModdingAPI"
+    };
+    
+    // macOS installs keep the executables inside the .app bundle
+    let candidates = [
+        game_path.join(executable_name),
+        game_path.join("Contents").join("MacOS").join(executable_name),
+    ];
+    
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+// Why a folder can't be used as the Mods folder, None when it looks fine
+fn mods_path_problem(path: &Path) -> Option<String> {
+    if !path.exists() {
+        return Some(format!("{} does not exist", path.display()));
+    }
+    
+    if !path.is_dir() {
+        return Some(format!("{} is not a folder", path.display()));
+    }
+    
+    // A fresh SMAPI install has an empty Mods folder, so the name alone is enough
+    let is_named_mods = path.file_name().is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case("Mods"));
+    if is_named_mods {
+        return None;
+    }
+    
+    let has_mods = fs::read_dir(path)
+        .map(|entries| entries.flatten().any(|entry| entry.path().is_dir() && find_manifest(&entry.path()).is_some()))
+        .unwrap_or(false);
+    if has_mods {
+        return None;
+    }
+    
+    Some(format!("{} isn't named Mods and doesn't contain any mod with a manifest.json", path.display()))
+}
+
 fn is_stardew_directory(path: &Path) -> bool {
     if cfg!(target_os = "windows") {
-        let executable_names = vec!["Stardew Valley.exe", "StardewValley.exe"];
+        let executable_names = ["Stardew Valley.exe", "StardewValley.exe"];
         return executable_names.iter().any(|name| path.join(name).exists());
     } else if cfg!(target_os = "macos") {
         // Check for .app bundle
@@ -673,7 +6249,7 @@ fn is_stardew_directory(path: &Path) -> bool {
             let contents_macos = path.join("Contents/MacOS");
             if contents_macos.exists() {
                 // Check for known executables
-                let executable_names = vec!["StardewValley", "Stardew Valley"];
+                let executable_names = ["StardewValley", "Stardew Valley"];
                 if executable_names.iter().any(|name| contents_macos.join(name).exists()) {
                     return true;
                 }
@@ -681,7 +6257,7 @@ fn is_stardew_directory(path: &Path) -> bool {
                 // Check for any executable files in Contents/MacOS
                 if let Ok(entries) = fs::read_dir(&contents_macos) {
                     for entry in entries.flatten() {
-                        if entry.file_type().map_or(false, |ft| ft.is_file()) {
+                        if entry.file_type().is_ok_and(|ft| ft.is_file()) {
                             return true;
                         }
                     }
@@ -689,7 +6265,7 @@ fn is_stardew_directory(path: &Path) -> bool {
             }
         } else {
             // For regular directories, check for executables and .app bundles
-            let executable_names = vec!["StardewValley", "Stardew Valley", "StardewModdingAPI", "Stardew Valley.app"];
+            let executable_names = ["StardewValley", "Stardew Valley", "StardewModdingAPI", "Stardew Valley.app"];
             if executable_names.iter().any(|name| path.join(name).exists()) {
                 return true;
             }
@@ -702,7 +6278,7 @@ fn is_stardew_directory(path: &Path) -> bool {
                     // Check for any files in Contents/MacOS
                     if let Ok(entries) = fs::read_dir(&contents_macos) {
                         for entry in entries.flatten() {
-                            if entry.file_type().map_or(false, |ft| ft.is_file()) {
+                            if entry.file_type().is_ok_and(|ft| ft.is_file()) {
                                 return true;
                             }
                         }
@@ -716,83 +6292,90 @@ fn is_stardew_directory(path: &Path) -> bool {
         }
     } else {
         // Linux
-        let executable_names = vec!["StardewValley", "Stardew Valley"];
+        let executable_names = ["StardewValley", "Stardew Valley"];
         return executable_names.iter().any(|name| path.join(name).exists());
     }
     
     false
 }
 
+fn parse_manifest(content: &str) -> Result<Manifest, AppError> {
+    parse_manifest_as(content)
+}
+
+fn parse_manifest_as<T: serde::de::DeserializeOwned>(content: &str) -> Result<T, AppError> {
+    // SMAPI manifests may contain a BOM, comments and trailing commas, so use a lenient parser
+    let content = content.trim_start_matches('\u{feff}');
+    json5::from_str::<T>(content).map_err(|e| AppError::Parse(format!("Invalid manifest: {}", e)))
+}
+
 fn parse_mod_folder(mod_path: &Path) -> Option<ModInfo> {
     let folder_name = mod_path.file_name()?.to_string_lossy().to_string();
     
-    // Skip hidden folders and system folders
-    if folder_name.starts_with('.') || folder_name.starts_with("__") {
+    // Skip system folders and backups left behind by interrupted updates
+    if folder_name.starts_with("__") || folder_name.ends_with(BACKUP_SUFFIX) {
         return None;
     }
     
-    let manifest_path = mod_path.join("manifest.json");
-    if manifest_path.exists() {
+    // Folders prefixed with a dot are mods disabled through SMAPI's naming convention
+    let enabled = !folder_name.starts_with('.');
+    let display_name = folder_name.strip_prefix('.').unwrap_or(&folder_name).to_string();
+    
+    if let Some(manifest_path) = find_manifest(mod_path) {
         match fs::read_to_string(&manifest_path) {
             Ok(manifest_content) => {
-                // Use regex to extract values directly from the text
-                use regex::Regex;
-                
-                // Extract Name
-                let name_re = Regex::new(r#""Name"\s*:\s*"([^"]+)""#).unwrap();
-                let name = name_re.captures(&manifest_content)
-                    .and_then(|caps| caps.get(1))
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_else(|| folder_name.clone());
-                
-                // Extract Version
-                let version_re = Regex::new(r#""Version"\s*:\s*"([^"]+)""#).unwrap();
-                let version = version_re.captures(&manifest_content)
-                    .and_then(|caps| caps.get(1))
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                
-                // Extract Author
-                let author_re = Regex::new(r#""Author"\s*:\s*"([^"]+)""#).unwrap();
-                let author = author_re.captures(&manifest_content)
-                    .and_then(|caps| caps.get(1))
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                
-                // Extract Description
-                let description_re = Regex::new(r#""Description"\s*:\s*"([^"]+)""#).unwrap();
-                let description = description_re.captures(&manifest_content)
-                    .and_then(|caps| caps.get(1))
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_else(|| "No description".to_string());
-                
-                // Extract UpdateKeys
-                let mut update_keys = Vec::new();
-                let update_keys_re = Regex::new(r#""UpdateKeys"\s*:\s*\[\s*([^\]]*)\s*\]"#).unwrap();
-                if let Some(caps) = update_keys_re.captures(&manifest_content) {
-                    if let Some(keys_str) = caps.get(1) {
-                        let keys_content = keys_str.as_str();
-                        let individual_key_re = Regex::new(r#""([^"]+)""#).unwrap();
-                        for cap in individual_key_re.captures_iter(keys_content) {
-                            if let Some(key) = cap.get(1) {
-                                update_keys.push(key.as_str().to_string());
-                            }
-                        }
+                match parse_manifest(&manifest_content) {
+                    Ok(manifest) => {
+                        let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+                        let mod_type = detect_mod_type(
+                            manifest_path.parent().unwrap_or(mod_path),
+                            manifest.entry_dll.as_deref(),
+                            manifest.content_pack_for.as_ref().map(|target| target.unique_id.as_str()),
+                        );
+                        let invalid = unique_id_problem(manifest.unique_id.as_deref());
+                        
+                        return Some(ModInfo {
+                            name: non_empty(manifest.name).unwrap_or_else(|| display_name.clone()),
+                            version: non_empty(manifest.version).unwrap_or_else(|| "Unknown".to_string()),
+                            author: non_empty(manifest.author).unwrap_or_else(|| "Unknown".to_string()),
+                            description: non_empty(manifest.description).unwrap_or_else(|| "No description".to_string()),
+                            folder_name: folder_name.clone(),
+                            enabled,
+                            update_keys: manifest.update_keys,
+                            unique_id: manifest.unique_id.map(|id| id.trim().to_string()).unwrap_or_default(),
+                            dependencies: manifest.dependencies
+                                .into_iter()
+                                .filter(|dependency| !dependency.unique_id.trim().is_empty())
+                                .map(|dependency| Dependency {
+                                    unique_id: dependency.unique_id.trim().to_string(),
+                                    minimum_version: dependency.minimum_version,
+                                    is_required: dependency.is_required,
+                                })
+                                .collect(),
+                            content_pack_for: manifest.content_pack_for
+                                .map(|target| target.unique_id.trim().to_string())
+                                .filter(|id| !id.is_empty()),
+                            minimum_api_version: non_empty(manifest.minimum_api_version).map(|version| version.trim().to_string()),
+                            size_bytes: 0,
+                            mod_type,
+                            has_config: manifest_path.with_file_name("config.json").is_file(),
+                            invalid,
+                            staged: false,
+                            needs_folder: false,
+                            fingerprint: None,
+                            load_order: manifest.load_order,
+                            loaded: enabled,
+                        });
+                    },
+                    Err(e) => {
+                        error!("Error parsing manifest.json for {}: {}", folder_name, e);
+                        return Some(invalid_mod_info(mod_path, &manifest_path, folder_name, enabled, format!("manifest.json could not be parsed: {}", e.message())));
                     }
                 }
-                
-                return Some(ModInfo {
-                    name,
-                    version,
-                    author,
-                    description,
-                    folder_name: folder_name.clone(),
-                    enabled: true,
-                    update_keys,
-                });
             },
             Err(e) => {
-                eprintln!("Error reading manifest.json for {}: {}", folder_name, e);
+                error!("Error reading manifest.json for {}: {}", folder_name, e);
+                return Some(invalid_mod_info(mod_path, &manifest_path, folder_name, enabled, format!("manifest.json could not be read: {}", e)));
             }
         }
     }
@@ -811,13 +6394,26 @@ fn parse_mod_folder(mod_path: &Path) -> Option<ModInfo> {
         
         if has_mod_files {
             return Some(ModInfo {
-                name: folder_name.clone(),
+                name: display_name,
                 version: "Unknown".to_string(),
                 author: "Unknown".to_string(),
                 description: "No manifest found - detected mod files".to_string(),
                 folder_name,
-                enabled: true,
+                enabled,
                 update_keys: Vec::new(),
+                unique_id: String::new(),
+                dependencies: Vec::new(),
+                content_pack_for: None,
+                minimum_api_version: None,
+                size_bytes: 0,
+                mod_type: detect_mod_type(mod_path, None, None),
+                has_config: mod_path.join("config.json").is_file(),
+                invalid: Some("Missing manifest.json".to_string()),
+                staged: false,
+                needs_folder: false,
+                fingerprint: None,
+                load_order: None,
+                loaded: enabled,
             });
         }
     }
@@ -825,10 +6421,196 @@ fn parse_mod_folder(mod_path: &Path) -> Option<ModInfo> {
     None
 }
 
+// A mod folder whose manifest is there but unusable, shown with the reason instead of hidden
+fn invalid_mod_info(mod_path: &Path, manifest_path: &Path, folder_name: String, enabled: bool, reason: String) -> ModInfo {
+    let manifest_dir = manifest_path.parent().unwrap_or(mod_path);
+    ModInfo {
+        name: folder_name.strip_prefix('.').unwrap_or(&folder_name).to_string(),
+        version: "Unknown".to_string(),
+        author: "Unknown".to_string(),
+        description: "Invalid manifest".to_string(),
+        folder_name,
+        enabled,
+        update_keys: Vec::new(),
+        unique_id: String::new(),
+        dependencies: Vec::new(),
+        content_pack_for: None,
+        minimum_api_version: None,
+        size_bytes: 0,
+        mod_type: detect_mod_type(manifest_dir, None, None),
+        has_config: manifest_dir.join("config.json").is_file(),
+        invalid: Some(reason),
+        staged: false,
+        needs_folder: false,
+        fingerprint: None,
+        load_order: None,
+        loaded: enabled,
+    }
+}
+
+// SMAPI only accepts letters, digits, underscores, dots and dashes in a UniqueID
+fn unique_id_problem(unique_id: Option<&str>) -> Option<String> {
+    let unique_id = unique_id.map(str::trim).unwrap_or_default();
+    if unique_id.is_empty() {
+        return Some("The manifest has no UniqueID".to_string());
+    }
+    
+    let is_valid = unique_id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if !is_valid {
+        return Some(format!("The UniqueID {} contains invalid characters", unique_id));
+    }
+    
+    None
+}
+
+// SMAPI refuses to load any of the loaded mods sharing a UniqueID.
+// Returns the indices of the mods that were flagged.
+fn flag_duplicate_ids(mods: &mut [ModInfo]) -> Vec<usize> {
+    let mut by_id: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, mod_info) in mods.iter().enumerate() {
+        if mod_info.loaded && !mod_info.unique_id.is_empty() {
+            by_id.entry(mod_info.unique_id.to_lowercase()).or_default().push(index);
+        }
+    }
+    
+    let mut flagged = Vec::new();
+    for indices in by_id.values().filter(|indices| indices.len() > 1) {
+        let folders: Vec<String> = indices.iter().map(|&index| mods[index].folder_name.clone()).collect();
+        for &index in indices {
+            let others: Vec<&str> = folders.iter().map(String::as_str).filter(|folder| *folder != mods[index].folder_name).collect();
+            mods[index].invalid.get_or_insert_with(|| format!("Duplicate UniqueID, also used by {}", others.join(", ")));
+            flagged.push(index);
+        }
+    }
+    
+    flagged.sort_unstable();
+    flagged
+}
+
+fn detect_mod_type(mod_dir: &Path, entry_dll: Option<&str>, content_pack_for: Option<&str>) -> ModType {
+    if entry_dll.is_some_and(|dll| !dll.trim().is_empty()) {
+        return ModType::SmapiDll;
+    }
+    
+    let for_content_patcher = content_pack_for.is_some_and(|id| id.trim().eq_ignore_ascii_case("Pathoschild.ContentPatcher"));
+    if for_content_patcher || mod_dir.join("content.json").is_file() {
+        return ModType::ContentPatcher;
+    }
+    
+    // Old-style replacements ship .xnb files meant to go into the game's Content folder
+    if contains_xnb(mod_dir) {
+        return ModType::Xnb;
+    }
+    
+    ModType::Unknown
+}
+
+fn contains_xnb(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    
+    entries.flatten().any(|entry| match entry.path().symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => contains_xnb(&entry.path()),
+        Ok(metadata) if metadata.is_file() => entry.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xnb")),
+        _ => false,
+    })
+}
+
+fn init_logging() -> Option<tracing_appender::non_blocking::WorkerGuard> {
+    use tracing_subscriber::prelude::*;
+    
+    // Update checks log at debug level, which is what bug reports need. RUST_LOG still wins for development
+    let log_level = get_settings().map(|settings| settings.log_level).unwrap_or_else(|_| default_log_level());
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| log_filter(&log_level).unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info,stardew_mod_manager_lib=debug")));
+    let (filter, filter_handle) = tracing_subscriber::reload::Layer::new(filter);
+    let _ = LOG_FILTER.set(filter_handle);
+    
+    // Packaged apps have no console, so the file next to settings.json is what users can attach
+    let appender = get_config_dir().ok().and_then(|config_dir| {
+        fs::create_dir_all(&config_dir).ok()?;
+        tracing_appender::rolling::Builder::new()
+            .rotation(tracing_appender::rolling::Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(config_dir)
+            .ok()
+    });
+    
+    let (file_layer, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(tracing_subscriber::fmt::layer().with_ansi(false).with_writer(writer)), Some(guard))
+        }
+        None => (None, None),
+    };
+    
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(file_layer)
+        .init();
+    
+    guard
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let mut builder = tauri::Builder::default();
+    
+    // A browser opening an nxm:// link starts a second instance, hand the link to the running one
+    #[cfg(desktop)]
+    {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+        }));
+    }
+    
+    builder
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .setup(|app| {
+            use tauri_plugin_deep_link::DeepLinkExt;
+            
+            // Everything stored on disk depends on the config dir, so resolve it first
+            let config_dir = init_config_dir(app);
+            if let Some(guard) = init_logging() {
+                let _ = LOG_GUARD.set(guard);
+            }
+            match (config_dir, portable_config_dir()) {
+                (Err(e), _) => warn!("Failed to resolve the app config directory, using the legacy location: {}", e),
+                (Ok(()), Some(portable_dir)) => info!("Found {}, storing app data in {}", PORTABLE_MARKER, portable_dir.display()),
+                (Ok(()), None) => {}
+            }
+            migrate_legacy_config();
+            
+            // Installers register the scheme, portable builds and dev runs need it done at startup
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                warn!("Failed to register the nxm:// handler: {}", e);
+            }
+            
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_nxm_url(&handle, url.as_str());
+                }
+            });
+            
+            // The app may have been started by an nxm:// link
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    handle_nxm_url(app.handle(), url.as_str());
+                }
+            }
+            
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             detect_stardew_valley, 
             scan_mods, 
@@ -839,8 +6621,251 @@ pub fn run() {
             open_url,
             open_folder,
             check_single_mod_update_frontend,
-            update_manifest_version
+            update_manifest_version,
+            set_mod_enabled,
+            delete_mod,
+            save_profile,
+            list_profiles,
+            apply_profile,
+            check_dependencies,
+            install_mod_from_zip,
+            open_smapi_log,
+            launch_game,
+            set_manual_game_path,
+            update_mods,
+            find_duplicate_mods,
+            export_mod_list,
+            import_mod_list,
+            preview_update,
+            disable_all_mods,
+            enable_all_mods,
+            backup_mods_folder,
+            restore_mods_backup,
+            detect_smapi,
+            check_smapi_compatibility,
+            open_log_file,
+            read_manifest,
+            validate_mods_path,
+            mods_depending_on,
+            scan_mods_streaming,
+            list_backup_folders,
+            cleanup_backups,
+            get_nexus_mod_details,
+            download_nexus_file,
+            verify_mod,
+            verify_all_mods,
+            check_compatibility_database,
+            rename_mod_folder,
+            open_mod_folder,
+            pin_mod,
+            unpin_mod,
+            read_mod_config,
+            move_mod,
+            set_mods_enabled_by_author,
+            get_nexus_changelog,
+            search_mods,
+            detect_game_version,
+            stage_mod,
+            unstage_mod,
+            find_content_conflicts,
+            check_app_update,
+            update_all_mods,
+            organize_loose_mod,
+            parse_smapi_errors,
+            mod_fingerprint,
+            get_nexus_primary_file,
+            set_mod_note,
+            get_mod_notes,
+            cleanup_temp_downloads,
+            preflight_check,
+            set_log_level,
+            import_mods_from_folder,
+            supported_update_providers,
+            find_mod_containing_file,
+            open_mod_homepage,
+            check_updates_via_smapi_api,
+            repair_mod,
+            suggest_load_order,
+            snapshot_mod,
+            restore_mod_snapshot,
+            find_missing_frameworks,
+            get_nexus_tracked_mods
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Scratch directory under the system temp dir, removed again when the test ends
+    struct TempDir(PathBuf);
+    
+    impl TempDir {
+        fn new(label: &str) -> Self {
+            let path = unique_temp_path(label);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+    
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+    
+    fn write_mod(mods_dir: &Path, folder_name: &str, unique_id: &str) {
+        let mod_dir = mods_dir.join(folder_name);
+        fs::create_dir_all(&mod_dir).unwrap();
+        let manifest = format!(r#"{{"Name": "{}", "Version": "1.0.0", "Author": "Someone", "UniqueID": "{}"}}"#, folder_name, unique_id);
+        fs::write(mod_dir.join("manifest.json"), manifest).unwrap();
+    }
+    
+    #[test]
+    fn corrupt_update_archive_restores_the_original_mod() {
+        let temp = TempDir::new("test-corrupt-update");
+        let mods_dir = temp.0.join("Mods");
+        let mod_dir = mods_dir.join("SomeMod");
+        fs::create_dir_all(&mod_dir).unwrap();
+        fs::write(mod_dir.join("manifest.json"), r#"{"Name": "Some Mod", "Version": "1.0.0", "UniqueID": "Someone.SomeMod"}"#).unwrap();
+        fs::write(mod_dir.join("config.json"), r#"{"Enabled": true}"#).unwrap();
+        
+        // Zip magic bytes followed by garbage, so it gets as far as the zip reader
+        let archive_path = temp.0.join("update.zip");
+        fs::write(&archive_path, b"PK\x03\x04this is not a real zip archive").unwrap();
+        
+        let result = install_update_archive(&archive_path, &mods_dir, "SomeMod", &mut |_, _| {});
+        
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(mod_dir.join("manifest.json")).unwrap(),
+            r#"{"Name": "Some Mod", "Version": "1.0.0", "UniqueID": "Someone.SomeMod"}"#
+        );
+        assert_eq!(fs::read_to_string(mod_dir.join("config.json")).unwrap(), r#"{"Enabled": true}"#);
+        assert!(!mods_dir.join(format!("SomeMod{}", BACKUP_SUFFIX)).exists());
+    }
+    
+    #[test]
+    fn zip_entries_escaping_the_target_are_skipped() {
+        use std::io::Write;
+        
+        let temp = TempDir::new("test-zip-slip");
+        let archive_path = temp.0.join("evil.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        let options = zip::write::FileOptions::default();
+        writer.start_file("SomeMod/manifest.json", options).unwrap();
+        writer.write_all(br#"{"Name": "Some Mod", "UniqueID": "Someone.SomeMod"}"#).unwrap();
+        writer.start_file("../evil", options).unwrap();
+        writer.write_all(b"should never be written").unwrap();
+        writer.finish().unwrap();
+        
+        let extract_to = temp.0.join("extracted");
+        let skipped = extract_zip(&archive_path, &extract_to, false, &mut |_, _| {}).unwrap();
+        
+        assert_eq!(skipped, vec!["../evil".to_string()]);
+        assert!(!temp.0.join("evil").exists());
+        assert!(extract_to.join("SomeMod").join("manifest.json").is_file());
+    }
+    
+    #[test]
+    fn parse_update_key_normalizes_keys() {
+        // Key, then the expected provider, id and subkey
+        type Expected<'a> = Option<(&'a str, &'a str, Option<&'a str>)>;
+        let cases: [(&str, Expected); 9] = [
+            ("Nexus:1234", Some(("nexus", "1234", None))),
+            ("Nexus: 1234", Some(("nexus", "1234", None))),
+            (" nexus : 1234 ", Some(("nexus", "1234", None))),
+            ("NEXUS:1234", Some(("nexus", "1234", None))),
+            ("GitHub:Pathoschild/SMAPI", Some(("github", "Pathoschild/SMAPI", None))),
+            ("Nexus:1234@main", Some(("nexus", "1234", Some("main")))),
+            ("GitHub: Someone/Repo @ v2", Some(("github", "Someone/Repo", Some("v2")))),
+            ("Nexus:1234@", Some(("nexus", "1234", None))),
+            ("Nexus:", None),
+        ];
+        
+        for (key, expected) in cases {
+            let parsed = parse_update_key(key);
+            let parsed = parsed.as_ref().map(|(provider, id, subkey)| (provider.as_str(), *id, *subkey));
+            assert_eq!(parsed, expected, "update key {:?}", key);
+        }
+        assert_eq!(parse_update_key("1234"), None);
+        assert_eq!(parse_update_key(":1234"), None);
+    }
+    
+    #[test]
+    fn equivalent_versions_are_not_updates() {
+        assert_eq!(normalize_version("0.9"), normalize_version("0.9.0"));
+        assert_eq!(normalize_version("1.2.3+5"), normalize_version("1.2.3"));
+        assert_eq!(normalize_version("v1.02"), normalize_version("1.2"));
+        
+        assert!(!version_compare("0.9", "0.9.0"));
+        assert!(!version_compare("0.9.0", "0.9"));
+        assert!(!version_compare("1.2.3", "1.2.3+5"));
+        assert!(!version_compare("1.2.3+5", "1.2.3"));
+        
+        // Real differences must still show up
+        assert!(version_compare("0.9", "0.9.1"));
+        assert!(version_compare("1.2.3-beta", "1.2.3"));
+        assert!(!version_compare("1.2.4", "1.2.3+5"));
+    }
+    
+    #[test]
+    fn update_keys_accept_a_string_or_an_array() {
+        let single = parse_manifest(r#"{ "Name": "Some Mod", "UniqueID": "Someone.SomeMod", "UpdateKeys": "Nexus:1234" }"#).unwrap();
+        let array = parse_manifest(r#"{ "Name": "Some Mod", "UniqueID": "Someone.SomeMod", "UpdateKeys": ["Nexus:1234"] }"#).unwrap();
+        let missing = parse_manifest(r#"{ "Name": "Some Mod", "UniqueID": "Someone.SomeMod" }"#).unwrap();
+        
+        assert_eq!(single.update_keys, vec!["Nexus:1234".to_string()]);
+        assert_eq!(single.update_keys, array.update_keys);
+        assert!(missing.update_keys.is_empty());
+    }
+    
+    #[test]
+    fn failed_install_move_restores_the_replaced_mod() {
+        let temp = TempDir::new("test-replace");
+        let mods_dir = temp.0.join("Mods");
+        write_mod(&mods_dir, ".SomeMod", "Someone.SomeMod");
+        write_mod(&mods_dir, "Group/SomeMod Copy", "Someone.SomeMod");
+        fs::write(mods_dir.join(".SomeMod/config.json"), "{ \"Enabled\": true }").unwrap();
+        
+        // A staging folder that doesn't exist makes move_dir fail
+        let existing = vec![".SomeMod".to_string(), "Group/SomeMod Copy".to_string()];
+        let result = replace_mod_folders(&temp.0.join("missing"), &mods_dir, &existing, ".SomeMod");
+        
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(mods_dir.join(".SomeMod/config.json")).unwrap(), "{ \"Enabled\": true }");
+        assert!(mods_dir.join("Group/SomeMod Copy/manifest.json").is_file());
+        assert!(!mods_dir.join(".SomeMod.backup").exists());
+        assert!(!mods_dir.join("Group/SomeMod Copy.backup").exists());
+    }
+    
+    #[test]
+    fn disable_all_mods_leaves_smapi_bundled_mods_alone() {
+        let temp = TempDir::new("test-disable-all");
+        let mods_dir = temp.0.join("Mods");
+        write_mod(&mods_dir, "ConsoleCommands", "SMAPI.ConsoleCommands");
+        write_mod(&mods_dir, "SaveBackup", "SMAPI.SaveBackup");
+        write_mod(&mods_dir, "ErrorHandler", "SMAPI.ErrorHandler");
+        write_mod(&mods_dir, "LookupAnything", "Pathoschild.LookupAnything");
+        write_mod(&mods_dir, "ChestsAnywhere", "Pathoschild.ChestsAnywhere");
+        
+        let result = disable_all_mods(mods_dir.to_string_lossy().to_string()).unwrap();
+        
+        assert_eq!(result.changed.len(), 2);
+        assert!(result.still_loaded.is_empty());
+        for bundled in SMAPI_BUNDLED_MODS {
+            assert!(mods_dir.join(bundled).is_dir());
+            assert!(!mods_dir.join(format!(".{}", bundled)).exists());
+        }
+        for user_mod in ["LookupAnything", "ChestsAnywhere"] {
+            assert!(!mods_dir.join(user_mod).exists());
+            assert!(mods_dir.join(format!(".{}", user_mod)).is_dir());
+        }
+        
+        assert_eq!(enable_all_mods(mods_dir.to_string_lossy().to_string()).unwrap().changed.len(), 2);
+        assert!(mods_dir.join("LookupAnything").is_dir());
+        assert!(mods_dir.join("ChestsAnywhere").is_dir());
+    }
+}
@@ -10,12 +10,15 @@ interface ModInfo {
   folder_name: string;
   enabled: boolean;
   update_keys: string[];
+  invalid: string | null;
+  loaded: boolean;
 }
 
 interface StardewInfo {
   game_path: string | null;
   mods_path: string | null;
   found: boolean;
+  warning: string | null;
 }
 
 interface UpdateInfo {
@@ -23,10 +26,54 @@ interface UpdateInfo {
   latest_version: string;
   update_available: boolean;
   download_url: string | null;
+  update_status: "UpToDate" | "Available" | "NoUpdateKey" | "ManualCheck" | "Offline" | "Pinned" | "RateLimited" | "Error";
+  checked_via: string;
+  failed_checks: string[];
+  rate_limit_reset: string | null;
+}
+
+interface AppError {
+  kind: "Network" | "Auth" | "NotFound" | "RateLimited" | "HasDependents" | "Io" | "Parse" | "Invalid";
+  message: string;
+  reset?: string | null;
+  dependents?: string[];
+}
+
+function errorMessage(err: unknown): string {
+  if (err instanceof Error) return err.message;
+  if (typeof err === "object" && err !== null && "message" in err) return (err as AppError).message;
+  return String(err);
+}
+
+interface UiState {
+  window_width: number | null;
+  window_height: number | null;
+  window_x: number | null;
+  window_y: number | null;
+  last_tab: string;
+}
+
+interface ModNote {
+  tags: string[];
+  note: string;
 }
 
 interface AppSettings {
   nexus_api_key: string | null;
+  update_cache_ttl_secs: number;
+  game_path: string | null;
+  mods_path: string | null;
+  max_retries: number;
+  include_prereleases: boolean;
+  offline: boolean;
+  request_timeout_secs: number;
+  pinned_versions: Record<string, string>;
+  ui_state: UiState;
+  proxy_url: string | null;
+  mod_notes: Record<string, ModNote>;
+  log_level: "error" | "warn" | "info" | "debug" | "trace";
+  enable_mechanism: "Rename" | "Marker";
+  disabled_mod_ids: string[];
 }
 
 function App() {
@@ -39,7 +86,29 @@ function App() {
   const [currentModBeingChecked, setCurrentModBeingChecked] = useState<string>("");
   const [updateProgress, setUpdateProgress] = useState({ current: 0, total: 0 });
   const [showSettings, setShowSettings] = useState(false);
-  const [settings, setSettings] = useState<AppSettings>({ nexus_api_key: null });
+  const [settings, setSettings] = useState<AppSettings>({
+    nexus_api_key: null,
+    update_cache_ttl_secs: 3600,
+    game_path: null,
+    mods_path: null,
+    max_retries: 3,
+    include_prereleases: false,
+    offline: false,
+    request_timeout_secs: 30,
+    pinned_versions: {},
+    ui_state: {
+      window_width: null,
+      window_height: null,
+      window_x: null,
+      window_y: null,
+      last_tab: "",
+    },
+    proxy_url: null,
+    mod_notes: {},
+    log_level: "debug",
+    enable_mechanism: "Rename",
+    disabled_mod_ids: [],
+  });
   const [tempApiKey, setTempApiKey] = useState("");
   const [lastUpdateCheck, setLastUpdateCheck] = useState<number | null>(null);
   const [showApiWarning, setShowApiWarning] = useState(false);
@@ -106,7 +175,7 @@ function App() {
       }
     } catch (err) {
       console.error("Error in initializeApp:", err);
-      setError(err instanceof Error ? err.message : `Unknown error: ${JSON.stringify(err)}`);
+      setError(err instanceof Error ? err.message : `Unknown error: ${errorMessage(err)}`);
     } finally {
       setLoading(false);
     }
@@ -121,7 +190,7 @@ function App() {
       setMods(modList);
     } catch (err) {
       console.error("Error in refreshMods:", err);
-      setError(err instanceof Error ? err.message : `Failed to refresh mods: ${JSON.stringify(err)}`);
+      setError(err instanceof Error ? err.message : `Failed to refresh mods: ${errorMessage(err)}`);
     } finally {
       setLoading(false);
     }
@@ -183,7 +252,7 @@ function App() {
       savePersistedUpdates(updateInfo, timestamp);
     } catch (err) {
       console.error("Error checking for updates:", err);
-      setError(err instanceof Error ? err.message : `Failed to check for updates: ${JSON.stringify(err)}`);
+      setError(err instanceof Error ? err.message : `Failed to check for updates: ${errorMessage(err)}`);
     } finally {
       setCheckingUpdates(false);
       setCurrentModBeingChecked("");
@@ -218,7 +287,7 @@ function App() {
       await invoke('open_folder', { path: stardewInfo.mods_path });
     } catch (err) {
       console.error('Failed to open mods folder:', err);
-      alert(`Failed to open mods folder: ${err}`);
+      alert(`Failed to open mods folder: ${errorMessage(err)}`);
     }
   }
 
@@ -271,7 +340,7 @@ function App() {
       console.error('Failed to verify update:', err);
       setVerificationStatus('Verification failed');
       setTimeout(() => {
-        alert(`Failed to verify update: ${err}`);
+        alert(`Failed to verify update: ${errorMessage(err)}`);
         setVerificationStatus(null);
       }, 1000);
     } finally {
@@ -345,9 +414,9 @@ function App() {
     } catch (err) {
       console.error('Failed to update manifest version:', err);
       console.error('Error details:', JSON.stringify(err, null, 2));
-      setVerificationStatus(`Failed to update manifest version: ${err}`);
+      setVerificationStatus(`Failed to update manifest version: ${errorMessage(err)}`);
       setTimeout(() => {
-        alert(`Failed to update manifest version: ${err}`);
+        alert(`Failed to update manifest version: ${errorMessage(err)}`);
         setVerificationStatus(null);
       }, 1000);
     } finally {
@@ -358,6 +427,7 @@ function App() {
 
   async function saveSettings() {
     const newSettings: AppSettings = {
+      ...settings,
       nexus_api_key: tempApiKey.trim() || null
     };
 
@@ -367,7 +437,7 @@ function App() {
       setShowSettings(false);
     } catch (err) {
       console.error("Error saving settings:", err);
-      setError(err instanceof Error ? err.message : `Failed to save settings: ${JSON.stringify(err)}`);
+      setError(err instanceof Error ? err.message : `Failed to save settings: ${errorMessage(err)}`);
     }
   }
 
@@ -452,7 +522,10 @@ function App() {
                 return (
                   <div key={mod.folder_name} className="mod-card">
                     <div className="mod-header">
-                      <h3>{mod.name}</h3>
+                      <h3>
+                        {mod.invalid && <span title={mod.invalid}>⚠️ </span>}
+                        {mod.name}
+                      </h3>
                       <div className="mod-version-info">
                         <span className="mod-version">v{mod.version}</span>
                         {updateInfo?.update_available && (
@@ -471,6 +544,23 @@ function App() {
                     )}
                     <div className="mod-footer">
                       <span className="mod-folder">Folder: {mod.folder_name}</span>
+                      {updateInfo?.update_status === "NoUpdateKey" && (
+                        <small className="mod-update-status">No update source</small>
+                      )}
+                      {updateInfo?.update_status === "Pinned" && (
+                        <small className="mod-update-status">Pinned</small>
+                      )}
+                      {updateInfo?.update_status === "Offline" && (
+                        <small className="mod-update-status">Offline</small>
+                      )}
+                      {updateInfo?.update_status === "RateLimited" && (
+                        <small className="mod-update-status">
+                          {updateInfo.rate_limit_reset ? `Nexus limit reached, resets at ${updateInfo.rate_limit_reset}` : "Nexus limit reached"}
+                        </small>
+                      )}
+                      {updateInfo?.update_status === "Error" && (
+                        <small className="mod-update-status">Update check failed</small>
+                      )}
                       {updateInfo && updateInfo.download_url && (
                         updateInfo.update_available ? (
                           <button 
@@ -480,7 +570,7 @@ function App() {
                           >
                             Update Available
                           </button>
-                        ) : updateInfo.latest_version === "Manual check" ? (
+                        ) : updateInfo.update_status === "ManualCheck" ? (
                           <button 
                             className="manual-check-button"
                             onClick={async () => {
@@ -488,7 +578,7 @@ function App() {
                                 await invoke('open_url', { url: updateInfo.download_url! });
                               } catch (err) {
                                 console.error('Failed to open URL:', err);
-                                alert(`Failed to open URL: ${err}`);
+                                alert(`Failed to open URL: ${errorMessage(err)}`);
                               }
                             }}
                           >
@@ -496,14 +586,17 @@ function App() {
                           </button>
                         ) : null
                       )}
-                      <label className="mod-toggle">
+                      <label
+                        className="mod-toggle"
+                        title={!mod.enabled && mod.loaded ? "Disabled in the manager only, SMAPI still loads this mod" : undefined}
+                      >
                         <input 
                           type="checkbox" 
                           checked={mod.enabled} 
                           onChange={() => {}}
                           disabled
                         />
-                        Enabled
+                        {!mod.enabled && mod.loaded ? "Enabled (still loaded by SMAPI)" : "Enabled"}
                       </label>
                     </div>
                   </div>
@@ -656,7 +749,7 @@ function App() {
                           setUpdateStep(2);
                         } catch (err) {
                           console.error('Failed to open URL:', err);
-                          alert(`Failed to open URL: ${err}`);
+                          alert(`Failed to open URL: ${errorMessage(err)}`);
                         }
                       }}
                     >